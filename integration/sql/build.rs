@@ -2,5 +2,6 @@
 // SPDX-License-Identifier: Apache-2.0
 
 fn main() {
+    #[cfg(feature = "extension-module")]
     pyo3_build_config::add_extension_module_link_args();
 }
@@ -1,6 +1,7 @@
 // Copyright 2018-2022 contributors to the OpenLineage project
 // SPDX-License-Identifier: Apache-2.0
 
+use sqlparser::ast::Ident;
 use sqlparser::dialect::Dialect;
 
 #[derive(Debug, Default)]
@@ -25,3 +26,34 @@ impl Dialect for BigQueryDialect {
             || ch == '`'
     }
 }
+
+// BigQuery lets whole path be quoted at once, like `project.dataset.table`, or only some of its
+// parts, like `project.dataset`.table, so quoted identifiers can contain several parts.
+pub(crate) fn split_quoted_path(idents: &[Ident]) -> Vec<Ident> {
+    idents
+        .iter()
+        .flat_map(|ident| match ident.quote_style {
+            Some('`') => ident
+                .value
+                .split('.')
+                .map(|part| Ident::with_quote('`', part))
+                .collect(),
+            _ => vec![ident.clone()],
+        })
+        .collect()
+}
+
+// INFORMATION_SCHEMA views can be qualified by both project and dataset or region, like
+// `project.dataset.INFORMATION_SCHEMA.TABLES`, which is one part more than tables have.
+pub(crate) fn is_information_schema_view(idents: &[Ident]) -> bool {
+    idents.len() == 4 && idents[2].value.eq_ignore_ascii_case("INFORMATION_SCHEMA")
+}
+
+// Partition decorator, like in `dataset.events$20220101`, selects single partition of the table.
+// It's split off, so that all partitions are reported as the same table.
+pub(crate) fn split_partition(name: &mut Ident) -> Option<String> {
+    let (table, partition) = name.value.split_once('$')?;
+    let partition = String::from(partition);
+    name.value = String::from(table);
+    Some(partition)
+}
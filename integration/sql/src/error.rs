@@ -0,0 +1,64 @@
+// Copyright 2018-2022 contributors to the OpenLineage project
+// SPDX-License-Identifier: Apache-2.0
+
+use std::fmt;
+
+/// Error returned when lineage can't be extracted from SQL.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum ParseError {
+    /// SQL text is not valid in the dialect. Line and column are 1-based and point
+    /// at the token where parser gave up.
+    Syntax {
+        message: String,
+        line: u64,
+        column: u64,
+    },
+    /// SQL is valid, but contains construct we can't extract lineage from yet.
+    /// `node` names the AST node, like `TableFactor::UNNEST`.
+    Unsupported { node: String, message: String },
+    /// There was no statement to parse.
+    EmptyInput,
+}
+
+impl ParseError {
+    pub(crate) fn unsupported<T: fmt::Debug>(kind: &str, node: &T, message: String) -> Self {
+        // Debug output of AST enums starts with variant name.
+        let variant: String = format!("{:?}", node)
+            .chars()
+            .take_while(|c| c.is_alphanumeric() || *c == '_')
+            .collect();
+        ParseError::Unsupported {
+            node: format!("{}::{}", kind, variant),
+            message,
+        }
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseError::Syntax {
+                message,
+                line,
+                column,
+            } => write!(f, "{} at line {}, column {}", message, line, column),
+            ParseError::Unsupported { message, .. } => write!(f, "{}", message),
+            ParseError::EmptyInput => write!(f, "Empty statement list"),
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// Problem found when parsing in tolerant mode. Statement that couldn't be parsed is skipped,
+/// while unsupported parts of otherwise parsed statement are left out of its lineage.
+#[cfg_attr(feature = "python", pyo3::pyclass)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ExtractionError {
+    /// Index of the statement, counting from the first statement of the first SQL text.
+    /// SQL text that can't be tokenized counts as single statement.
+    pub index: usize,
+    /// Text of the statement.
+    pub statement: String,
+    pub reason: ParseError,
+}
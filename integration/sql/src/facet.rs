@@ -0,0 +1,150 @@
+// Copyright 2018-2022 contributors to the OpenLineage project
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::BTreeMap;
+
+use crate::{ColumnLineage, DbTableMeta, LifecycleChange, TableRename};
+use serde::Serialize;
+
+pub const PRODUCER: &str = concat!(
+    "https://github.com/OpenLineage/OpenLineage/tree/",
+    env!("CARGO_PKG_VERSION"),
+    "/integration/sql"
+);
+
+pub const COLUMN_LINEAGE_SCHEMA_URL: &str = "https://openlineage.io/spec/facets/1-0-1/ColumnLineageDatasetFacet.json#/$defs/ColumnLineageDatasetFacet";
+
+pub const LIFECYCLE_STATE_CHANGE_SCHEMA_URL: &str = "https://openlineage.io/spec/facets/1-0-0/LifecycleStateChangeDatasetFacet.json#/$defs/LifecycleStateChangeDatasetFacet";
+
+// Input column as referenced in `inputFields` of ColumnLineageDatasetFacet.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct InputField {
+    pub namespace: String,
+    pub name: String,
+    pub field: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ColumnLineageField {
+    pub input_fields: Vec<InputField>,
+}
+
+// See spec/facets/ColumnLineageDatasetFacet.json
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ColumnLineageDatasetFacet {
+    #[serde(rename = "_producer")]
+    pub producer: String,
+    #[serde(rename = "_schemaURL")]
+    pub schema_url: String,
+    pub fields: BTreeMap<String, ColumnLineageField>,
+}
+
+impl ColumnLineageDatasetFacet {
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).expect("facet is always serializable")
+    }
+}
+
+// Groups column lineage by output table, producing one facet per output table.
+// Input columns we can't attribute to a table are omitted, since facet requires
+// dataset name for every input field.
+pub(crate) fn column_lineage_facets(
+    column_lineage: &[ColumnLineage],
+    namespace: &str,
+    producer: &str,
+) -> Vec<(DbTableMeta, ColumnLineageDatasetFacet)> {
+    let mut facets: BTreeMap<DbTableMeta, ColumnLineageDatasetFacet> = BTreeMap::new();
+    for column in column_lineage {
+        let table = match &column.descendant.origin {
+            Some(table) => table,
+            None => continue,
+        };
+        let input_fields = column
+            .lineage
+            .iter()
+            .filter_map(|input| {
+                input.origin.as_ref().map(|origin| InputField {
+                    namespace: namespace.to_string(),
+                    name: origin.qualified_name(),
+                    field: input.name.clone(),
+                })
+            })
+            .collect();
+        facets
+            .entry(table.clone())
+            .or_insert_with(|| ColumnLineageDatasetFacet {
+                producer: producer.to_string(),
+                schema_url: COLUMN_LINEAGE_SCHEMA_URL.to_string(),
+                fields: BTreeMap::new(),
+            })
+            .fields
+            .insert(
+                column.descendant.name.clone(),
+                ColumnLineageField { input_fields },
+            );
+    }
+    facets.into_iter().collect()
+}
+
+// Name of the table before it was renamed, as `previousIdentifier` of
+// LifecycleStateChangeDatasetFacet.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct PreviousIdentifier {
+    pub namespace: String,
+    pub name: String,
+}
+
+// See spec/facets/LifecycleStateChangeDatasetFacet.json
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LifecycleStateChangeDatasetFacet {
+    #[serde(rename = "_producer")]
+    pub producer: String,
+    #[serde(rename = "_schemaURL")]
+    pub schema_url: String,
+    pub lifecycle_state_change: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub previous_identifier: Option<PreviousIdentifier>,
+}
+
+impl LifecycleStateChangeDatasetFacet {
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).expect("facet is always serializable")
+    }
+}
+
+// One facet per output table whose lifecycle state was changed. Dataset has room for
+// a single facet, so table changed by several statements gets the state left by the last
+// of them. Changes have to be in order of statements.
+pub(crate) fn lifecycle_state_change_facets(
+    changes: &[LifecycleChange],
+    renames: &[TableRename],
+    namespace: &str,
+    producer: &str,
+) -> Vec<(DbTableMeta, LifecycleStateChangeDatasetFacet)> {
+    let mut facets: BTreeMap<DbTableMeta, LifecycleStateChangeDatasetFacet> = BTreeMap::new();
+    for change in changes {
+        let state = match change.operation.lifecycle_state_change() {
+            Some(state) => state,
+            None => continue,
+        };
+        let previous_identifier = renames
+            .iter()
+            .find(|rename| state == "RENAME" && rename.to == change.table)
+            .map(|rename| PreviousIdentifier {
+                namespace: namespace.to_string(),
+                name: rename.from.qualified_name(),
+            });
+        facets.insert(
+            change.table.clone(),
+            LifecycleStateChangeDatasetFacet {
+                producer: producer.to_string(),
+                schema_url: LIFECYCLE_STATE_CHANGE_SCHEMA_URL.to_string(),
+                lifecycle_state_change: state.to_string(),
+                previous_identifier,
+            },
+        );
+    }
+    facets.into_iter().collect()
+}
@@ -0,0 +1,187 @@
+// Copyright 2018-2022 contributors to the OpenLineage project
+// SPDX-License-Identifier: Apache-2.0
+
+// Tables that are left out of lineage. Catalogs of database's own metadata, like
+// `information_schema`, and pseudo-tables, like Oracle's `DUAL`, aren't datasets anyone
+// produces or consumes, so they are excluded by default. Callers can exclude more tables
+// with patterns.
+
+use std::str::FromStr;
+
+use regex::Regex;
+use sqlparser::dialect::{
+    Dialect, HiveDialect, MsSqlDialect, MySqlDialect, PostgreSqlDialect, RedshiftSqlDialect,
+    SQLiteDialect, SnowflakeDialect,
+};
+
+use crate::bigquery::BigQueryDialect;
+use crate::DbTableMeta;
+
+/// Pattern that qualified names of excluded tables, like `db.schema.table`, match.
+/// Glob patterns are matched case insensitively; `*` matches any sequence of characters,
+/// including dots, and `?` matches single character. Regex has to match the whole name.
+#[derive(Debug, Clone)]
+pub enum TablePattern {
+    Glob(String),
+    Regex(Regex),
+}
+
+impl TablePattern {
+    pub fn glob(pattern: &str) -> Self {
+        TablePattern::Glob(String::from(pattern))
+    }
+
+    pub fn regex(pattern: &str) -> Result<Self, String> {
+        Regex::new(&format!("^(?:{})$", pattern))
+            .map(TablePattern::Regex)
+            .map_err(|e| format!("invalid table pattern {}: {}", pattern, e))
+    }
+
+    pub fn matches(&self, table: &DbTableMeta) -> bool {
+        let name = table.qualified_name();
+        match self {
+            TablePattern::Glob(pattern) => glob_matches(pattern, &name),
+            TablePattern::Regex(regex) => regex.is_match(&name),
+        }
+    }
+}
+
+// Patterns prefixed with `regex:` are regular expressions, others are globs,
+// optionally prefixed with `glob:`.
+impl FromStr for TablePattern {
+    type Err = String;
+
+    fn from_str(pattern: &str) -> Result<Self, Self::Err> {
+        if let Some(regex) = pattern.strip_prefix("regex:") {
+            TablePattern::regex(regex)
+        } else {
+            Ok(TablePattern::glob(
+                pattern.strip_prefix("glob:").unwrap_or(pattern),
+            ))
+        }
+    }
+}
+
+fn glob_matches(pattern: &str, name: &str) -> bool {
+    let pattern: Vec<char> = pattern.to_lowercase().chars().collect();
+    let name: Vec<char> = name.to_lowercase().chars().collect();
+    let (mut p, mut n) = (0, 0);
+    // Position in pattern after the last `*`, and position in name it matched up to,
+    // to retry from with `*` matching one more character when the rest doesn't match.
+    let mut star: Option<(usize, usize)> = None;
+    while n < name.len() {
+        match pattern.get(p) {
+            Some('*') => {
+                star = Some((p + 1, n));
+                p += 1;
+            }
+            Some(c) if *c == '?' || *c == name[n] => {
+                p += 1;
+                n += 1;
+            }
+            _ => match star {
+                Some((after_star, matched)) => {
+                    star = Some((after_star, matched + 1));
+                    p = after_star;
+                    n = matched + 1;
+                }
+                None => return false,
+            },
+        }
+    }
+    pattern[p..].iter().all(|c| *c == '*')
+}
+
+// Objects that belong to the database itself. Names are compared case insensitively.
+struct SystemObjects {
+    // Databases, also called projects or catalogs, whose all tables are system ones.
+    databases: &'static [&'static str],
+    // Schemas whose all tables are system ones.
+    schemas: &'static [&'static str],
+    // Glob patterns of system tables, in whatever schema.
+    tables: &'static [&'static str],
+    // Glob patterns of tables that are system ones when their name isn't qualified.
+    // Qualified, the same name may refer to regular table.
+    unqualified: &'static [&'static str],
+}
+
+fn system_objects(dialect: &dyn Dialect) -> SystemObjects {
+    if dialect.is::<PostgreSqlDialect>() {
+        SystemObjects {
+            databases: &[],
+            schemas: &["information_schema", "pg_catalog", "pg_toast"],
+            tables: &[],
+            unqualified: &["pg_*"],
+        }
+    } else if dialect.is::<RedshiftSqlDialect>() {
+        SystemObjects {
+            databases: &[],
+            schemas: &["information_schema", "pg_catalog", "pg_internal"],
+            tables: &[],
+            unqualified: &["pg_*", "stl_*", "stv_*", "svl_*", "svv_*", "sys_*"],
+        }
+    } else if dialect.is::<SnowflakeDialect>() {
+        SystemObjects {
+            databases: &["snowflake"],
+            schemas: &["information_schema"],
+            tables: &[],
+            unqualified: &["dual"],
+        }
+    } else if dialect.is::<BigQueryDialect>() {
+        // Region qualified views, like `region-us`.INFORMATION_SCHEMA.JOBS, are in
+        // INFORMATION_SCHEMA too, and so are ones qualified by project as well, like
+        // project.`region-us`.INFORMATION_SCHEMA.JOBS.
+        SystemObjects {
+            databases: &[],
+            schemas: &["information_schema"],
+            tables: &["__tables__", "__tables_summary__"],
+            unqualified: &[],
+        }
+    } else if dialect.is::<MySqlDialect>() {
+        SystemObjects {
+            databases: &[],
+            schemas: &["information_schema", "mysql", "performance_schema", "sys"],
+            tables: &[],
+            unqualified: &["dual"],
+        }
+    } else if dialect.is::<MsSqlDialect>() || dialect.is::<HiveDialect>() {
+        SystemObjects {
+            databases: &[],
+            schemas: &["information_schema", "sys"],
+            tables: &[],
+            unqualified: &[],
+        }
+    } else if dialect.is::<SQLiteDialect>() {
+        SystemObjects {
+            databases: &[],
+            schemas: &[],
+            tables: &["sqlite_master", "sqlite_schema", "sqlite_temp_master"],
+            unqualified: &[],
+        }
+    } else {
+        // Generic dialect is used for databases that have no own, like Oracle.
+        SystemObjects {
+            databases: &[],
+            schemas: &["information_schema", "pg_catalog", "sys"],
+            tables: &[],
+            unqualified: &["dual"],
+        }
+    }
+}
+
+pub(crate) fn is_system_table(dialect: &dyn Dialect, table: &DbTableMeta) -> bool {
+    let objects = system_objects(dialect);
+    let any_equal = |names: &[&str], part: &Option<String>| match part {
+        Some(part) => names.iter().any(|name| name.eq_ignore_ascii_case(part)),
+        None => false,
+    };
+    let any_match = |patterns: &[&str]| {
+        patterns
+            .iter()
+            .any(|pattern| glob_matches(pattern, &table.name))
+    };
+    any_equal(objects.databases, &table.database)
+        || any_equal(objects.schemas, &table.schema)
+        || any_match(objects.tables)
+        || (table.parts.len() == 1 && any_match(objects.unqualified))
+}
@@ -0,0 +1,103 @@
+// Copyright 2018-2022 contributors to the OpenLineage project
+// SPDX-License-Identifier: Apache-2.0
+
+use sqlparser::ast::Ident;
+use sqlparser::dialect::{HiveDialect, MsSqlDialect, MySqlDialect};
+use sqlparser::keywords::ALL_KEYWORDS;
+
+use crate::{BigQueryDialect, CanonicalDialect};
+
+/// How dialect treats case of identifiers. Names of the same table spelled differently
+/// are normalized to a single form, so that they are reported as one dataset.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IdentifierCase {
+    /// Identifiers are kept as written, either because they are case sensitive, like
+    /// table names in BigQuery, or because dialect doesn't fold them.
+    Preserve,
+    /// Unquoted identifiers are folded to upper case, like in Snowflake.
+    Upper,
+    /// Unquoted identifiers are folded to lower case, like in Postgres.
+    Lower,
+    /// All identifiers, quoted or not, are folded to lower case, like in Redshift and Hive.
+    LowerAll,
+}
+
+impl IdentifierCase {
+    pub fn fold(&self, value: &str, quoted: bool) -> String {
+        match self {
+            IdentifierCase::Upper if !quoted => value.to_uppercase(),
+            IdentifierCase::Lower if !quoted => value.to_lowercase(),
+            IdentifierCase::LowerAll => value.to_lowercase(),
+            _ => value.to_string(),
+        }
+    }
+}
+
+/// Part of a table name as it was written in SQL.
+#[cfg_attr(feature = "python", pyo3::pyclass)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct NamePart {
+    /// Identifier without delimiters, with escaped quotes unescaped, and case not folded.
+    pub value: String,
+    /// Delimiter identifier was quoted with, like `"`, `` ` `` or `[`.
+    pub quote_style: Option<char>,
+}
+
+impl NamePart {
+    pub fn is_quoted(&self) -> bool {
+        self.quote_style.is_some()
+    }
+
+    /// Identifier as written in SQL, with delimiters and escaped quotes.
+    pub fn raw(&self) -> String {
+        match self.quote_style {
+            Some(quote) => quote_with(&self.value, quote),
+            None => self.value.clone(),
+        }
+    }
+}
+
+impl From<&Ident> for NamePart {
+    fn from(ident: &Ident) -> Self {
+        NamePart {
+            value: ident.value.clone(),
+            quote_style: ident.quote_style,
+        }
+    }
+}
+
+// Identifier that, read by the dialect, gives back `value`. It's quoted only if needed: when it
+// isn't a valid plain identifier, is a keyword, or would have its case folded otherwise.
+pub(crate) fn render(dialect: &dyn CanonicalDialect, value: &str) -> String {
+    let mut chars = value.chars();
+    let plain = match chars.next() {
+        Some(first) => {
+            dialect.is_identifier_start(first) && chars.all(|c| dialect.is_identifier_part(c))
+        }
+        None => false,
+    };
+    let keyword = ALL_KEYWORDS
+        .binary_search(&value.to_uppercase().as_str())
+        .is_ok();
+    if plain && !keyword && dialect.identifier_case().fold(value, false) == value {
+        return String::from(value);
+    }
+    let dialect = dialect.as_base();
+    let quote = if dialect.is::<BigQueryDialect>()
+        || dialect.is::<MySqlDialect>()
+        || dialect.is::<HiveDialect>()
+    {
+        '`'
+    } else if dialect.is::<MsSqlDialect>() {
+        '['
+    } else {
+        '"'
+    };
+    quote_with(value, quote)
+}
+
+fn quote_with(value: &str, quote: char) -> String {
+    let close = if quote == '[' { ']' } else { quote };
+    let escaped = value.replace(close, &format!("{}{}", close, close));
+    format!("{}{}{}", quote, escaped, close)
+}
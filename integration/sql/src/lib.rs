@@ -2,46 +2,85 @@
 // SPDX-License-Identifier: Apache-2.0
 
 mod bigquery;
+mod error;
+mod facet;
+mod filter;
+mod identifier;
+mod lifecycle;
+mod lineage;
+mod mapping;
+mod naming;
+mod occurrence;
+mod operation;
+#[cfg(feature = "python")]
+mod python;
+mod remote;
+mod rename;
+mod schema;
+mod session;
+mod temporary;
+mod tokens;
 
-use std::collections::hash_map::DefaultHasher;
-use std::collections::HashSet;
+use std::cmp::Ordering;
+use std::collections::{HashMap, HashSet};
 use std::hash::{Hash, Hasher};
 use std::sync::Arc;
 
 pub use bigquery::BigQueryDialect;
-use pyo3::basic::CompareOp;
-use pyo3::exceptions::{PyRuntimeError, PyTypeError};
-use pyo3::prelude::*;
+pub use error::{ExtractionError, ParseError};
+pub use facet::{
+    ColumnLineageDatasetFacet, ColumnLineageField, InputField, LifecycleStateChangeDatasetFacet,
+    PreviousIdentifier, PRODUCER,
+};
+pub use filter::TablePattern;
+pub use identifier::{IdentifierCase, NamePart};
+use lineage::{expr_name, rename_columns, resolve_column, ExprRefs, OutputColumn, Relation};
+pub use lineage::{ColumnLineage, ColumnMeta};
+pub use mapping::{NameField, NameMapping};
+pub use naming::{Connection, DatasetName};
+use occurrence::TableReference;
+pub use occurrence::{TableOccurrence, TableRole};
+pub use operation::{DatasetOperation, LifecycleChange, Operation, TableRename};
+pub use schema::{InMemorySchemaProvider, JsonSchemaProvider, SchemaProvider};
+use session::{Session, ViewDefinition};
 use sqlparser::ast::{
-    Expr, Ident, Query, Select, SelectItem, SetExpr, Statement, TableAlias, TableFactor, With,
+    AlterTableOperation, Expr, Ident, MergeClause, ObjectName, ObjectType, Query, Select,
+    SelectItem, SetExpr, Statement, TableAlias, TableFactor, With,
 };
 use sqlparser::dialect::{
     AnsiDialect, Dialect, GenericDialect, HiveDialect, MsSqlDialect, MySqlDialect,
     PostgreSqlDialect, RedshiftSqlDialect, SQLiteDialect, SnowflakeDialect,
 };
-use sqlparser::parser::Parser;
+use tokens::LocatedToken;
+pub use tokens::Location;
 
 pub trait CanonicalDialect: Dialect {
-    fn canonical_name(&self, name: String) -> Option<String>;
+    fn canonical_name(&self, ident: &Ident) -> String;
+    fn identifier_case(&self) -> IdentifierCase;
     fn as_base(&self) -> &dyn Dialect;
 }
 
 impl<T: Dialect> CanonicalDialect for T {
-    fn canonical_name(&self, name: String) -> Option<String> {
-        match name.chars().next() {
-            Some(x) => {
-                if self.is_delimited_identifier_start(x) {
-                    let mut chars = name.chars();
-                    chars.next();
-                    chars.next_back();
-                    Some(chars.as_str().to_string())
-                } else {
-                    Some(name)
-                }
-            }
-            _ => None,
+    // Folds case of identifier the way dialect does. Tokenizer already stripped delimiters
+    // of quoted identifier, and unescaped quotes inside it.
+    fn canonical_name(&self, ident: &Ident) -> String {
+        self.identifier_case()
+            .fold(&ident.value, ident.quote_style.is_some())
+    }
+
+    fn identifier_case(&self) -> IdentifierCase {
+        let dialect = self.as_base();
+        if dialect.is::<SnowflakeDialect>() || dialect.is::<AnsiDialect>() {
+            IdentifierCase::Upper
+        } else if dialect.is::<PostgreSqlDialect>() {
+            IdentifierCase::Lower
+        } else if dialect.is::<RedshiftSqlDialect>() || dialect.is::<HiveDialect>() {
+            IdentifierCase::LowerAll
+        } else {
+            IdentifierCase::Preserve
         }
     }
+
     fn as_base(&self) -> &dyn Dialect {
         self
     }
@@ -50,124 +89,705 @@ impl<T: Dialect> CanonicalDialect for T {
 // Context struct serves as generic holder of an all information we currently have about
 // SQL statements that we have parsed so far.
 //
-// TODO: properly handle "nested" and linked contexts.
-// For example, right now when handling multiple statements we don't create contexts.
-// This is because we want to interpret inputs and outputs of a multiple statements
-// as a single list of inputs and outputs - since that's what's our parser contract.
-// Caller like Airflow Extractor does not know how much queries were executed separated by commas
-// since it's supposed to be opaque blob - so we want to keep input and output info.
-// However, aliases are lost when going from statement to statement, so we need to handle that.
+// Names visible at one level of a query: query with its WITH clause, or SELECT with its FROM.
+// Nested queries - CTEs, subqueries in FROM or in expressions - get their own scope, so names
+// they define don't leak outside, while they still see names of queries containing them.
+#[derive(Debug, Default)]
+struct Scope {
+    // CTEs defined in WITH clause of this query, with their columns. Recursive CTE
+    // has no columns while its own definition is parsed.
+    ctes: HashMap<DbTableMeta, Option<Vec<OutputColumn>>>,
+    // Relations from FROM clause parsed so far.
+    relations: Vec<Relation>,
+}
+
+// Context of single statement. When handling multiple statements, each gets fresh context,
+// and results are merged - caller like Airflow Extractor does not know how much queries were
+// executed, since it's supposed to be opaque blob.
 #[derive(Debug)]
 struct Context {
-    // Set of aliases we discovered in this query. We don't want to return alias as input and
-    // output, because they have no sense in outside of query they were defined
-    aliases: HashSet<DbTableMeta>,
+    // Stack of scopes of queries we're in, innermost last. CTE names are looked up here,
+    // so that we don't return them as inputs or outputs.
+    scopes: Vec<Scope>,
     // Tables used as input to this query. "Input" is defined liberally, query does not have
     // to read data to be treated as input - it's sufficient that it's referenced in a query somehow
     inputs: HashSet<DbTableMeta>,
     // Tables used as output to this query. Same as input, they have to be referenced - data does
     // not have to be actually written as a result of execution.
     outputs: HashSet<DbTableMeta>,
-    // Some databases allow to specify default schema. When schema for table is not referenced,
-    // we're using this default as it.
-    default_schema: Option<String>,
+    // For each column of output table, set of input columns it's computed from.
+    column_lineage: HashMap<ColumnMeta, HashSet<ColumnMeta>>,
+    // Database assigned to tables whose schema is known, but database is not.
+    default_database: Option<String>,
+    // Schemas in which tables without schema are looked for, in order.
+    search_path: Vec<String>,
+    // Knows columns of tables and their fully qualified names, if caller provided it.
+    schema_provider: Option<Arc<dyn SchemaProvider>>,
     // Dialect used in this statements.
     dialect: Arc<dyn CanonicalDialect>,
+    // If set, unsupported parts of the statement are skipped instead of failing it.
+    tolerant: bool,
+    // Errors of parts of the statement that were skipped.
+    errors: Vec<ParseError>,
+    // Outputs that are temporary tables.
+    temporary: HashSet<DbTableMeta>,
+    // Operations applied to outputs.
+    operations: HashSet<DatasetOperation>,
+    // If not set, system tables are left out of lineage.
+    include_system_tables: bool,
+    // Tables left out of lineage.
+    exclude: Vec<TablePattern>,
+    // Applied to names of tables once statement is parsed.
+    name_mapping: Option<Arc<NameMapping>>,
+    // Names of inputs and outputs as written, to find where they are in the statement.
+    references: Vec<TableReference>,
+    // Views created so far in the script, including by this statement.
+    views: HashMap<DbTableMeta, ViewDefinition>,
+    // Tables renamed, with their old and new names.
+    renames: HashSet<TableRename>,
 }
 
 impl Context {
-    fn default() -> Context {
+    fn new(
+        dialect: Arc<dyn CanonicalDialect>,
+        options: &ParseOptions,
+        session: &Session,
+    ) -> Context {
         Context {
-            aliases: HashSet::new(),
+            scopes: vec![Scope::default()],
             inputs: HashSet::new(),
             outputs: HashSet::new(),
-            default_schema: None,
-            dialect: Arc::new(SnowflakeDialect),
+            column_lineage: HashMap::new(),
+            default_database: session.database.clone(),
+            search_path: session.search_path.clone(),
+            schema_provider: options.schema_provider.clone(),
+            dialect,
+            tolerant: options.tolerant,
+            errors: vec![],
+            temporary: HashSet::new(),
+            operations: HashSet::new(),
+            include_system_tables: options.include_system_tables,
+            exclude: options.exclude.clone(),
+            name_mapping: options.name_mapping.clone(),
+            references: vec![],
+            views: session.views.clone(),
+            renames: HashSet::new(),
         }
     }
 
-    fn new(dialect: Arc<dyn CanonicalDialect>, default_schema: Option<&str>) -> Context {
-        Context {
-            aliases: HashSet::new(),
-            inputs: HashSet::new(),
-            outputs: HashSet::new(),
-            default_schema: default_schema.map(String::from),
-            dialect,
+    // In tolerant mode remembers the error so that caller can skip the unsupported node,
+    // otherwise returns it.
+    fn skip_unsupported(&mut self, error: ParseError) -> Result<(), ParseError> {
+        if self.tolerant {
+            self.errors.push(error);
+            Ok(())
+        } else {
+            Err(error)
+        }
+    }
+
+    // Fills in parts of the table name that were omitted in the query. Table without schema
+    // is looked for in schemas of the search path, in order, and the first one schema provider
+    // knows it in is used - like database would do. If provider doesn't know it in any,
+    // or there is no provider, the first schema of the search path is assumed.
+    fn resolve_table(&self, table: DbTableMeta) -> DbTableMeta {
+        // Defaults of the session don't apply to tables on other servers.
+        if table.server.is_some() {
+            return table;
+        }
+        let candidates: Vec<DbTableMeta> = match (&table.schema, self.search_path.as_slice()) {
+            (None, [_, ..]) => self
+                .search_path
+                .iter()
+                .map(|schema| DbTableMeta {
+                    schema: Some(schema.clone()),
+                    ..table.clone()
+                })
+                .collect(),
+            _ => vec![table.clone()],
+        };
+        let resolved = self
+            .schema_provider
+            .as_ref()
+            .and_then(|provider| {
+                candidates.iter().find_map(|candidate| {
+                    let known = provider.resolve_table(candidate)?;
+                    Some(self.fold_resolved(candidate, known))
+                })
+            })
+            .unwrap_or_else(|| candidates[0].clone());
+        let database = match (&resolved.database, &resolved.schema) {
+            (None, Some(_)) => self.default_database.clone(),
+            _ => resolved.database.clone(),
+        };
+        DbTableMeta {
+            database,
+            partitions: table.partitions,
+            wildcard: table.wildcard,
+            parts: table.parts,
+            original_name: table.original_name,
+            ..resolved
+        }
+    }
+
+    // Provider spells names the way its schema does, which needn't be how dialect folds them.
+    // Parts the query named are kept, and the ones provider filled in are folded like
+    // unquoted identifiers.
+    fn fold_resolved(&self, table: &DbTableMeta, known: DbTableMeta) -> DbTableMeta {
+        let case = self.dialect.identifier_case();
+        let fold = |part: &Option<String>, known: Option<String>| {
+            part.clone()
+                .or_else(|| known.map(|known| case.fold(&known, false)))
+        };
+        DbTableMeta {
+            server: fold(&table.server, known.server),
+            database: fold(&table.database, known.database),
+            schema: fold(&table.schema, known.schema),
+            ..table.clone()
+        }
+    }
+
+    // Tells if table is left out of lineage. It can still be read from, but neither it,
+    // nor its columns are reported.
+    fn is_excluded(&self, table: &DbTableMeta) -> bool {
+        (!self.include_system_tables && filter::is_system_table(self.dialect.as_base(), table))
+            || self.exclude.iter().any(|pattern| pattern.matches(table))
+    }
+
+    fn get_table_columns(&self, table: &DbTableMeta) -> Option<Vec<String>> {
+        self.schema_provider
+            .as_ref()
+            .and_then(|provider| provider.get_columns(table))
+    }
+
+    // Runs `f` in new scope, that is dropped when it returns.
+    fn in_scope<T, F>(&mut self, f: F) -> Result<T, ParseError>
+    where
+        F: FnOnce(&mut Context) -> Result<T, ParseError>,
+    {
+        self.scopes.push(Scope::default());
+        let result = f(self);
+        self.scopes.pop();
+        result
+    }
+
+    fn current_scope(&mut self) -> &mut Scope {
+        self.scopes.last_mut().expect("context has no scope")
+    }
+
+    // Finds CTE visible from current scope. Outer Option tells if there is CTE of that name.
+    fn find_cte(&self, name: &DbTableMeta) -> Option<&Option<Vec<OutputColumn>>> {
+        self.scopes
+            .iter()
+            .rev()
+            .find_map(|scope| scope.ctes.get(name))
+    }
+
+    // Table, or CTE, that name refers to, before it's resolved.
+    fn table_name(&self, name: &ObjectName) -> Result<DbTableMeta, ParseError> {
+        DbTableMeta::from_idents(&name.0, name.to_string(), self.dialect.as_ref(), false)
+    }
+
+    fn add_input(&mut self, table: &ObjectName) -> Result<(), ParseError> {
+        let name = self.table_name(table)?;
+        if self.find_cte(&name).is_none() {
+            let name = self.resolve_table(name);
+            // Reading view created earlier in the script reads its base tables. View itself
+            // isn't an input, so its name isn't recorded as an occurrence of one either.
+            if let Some(view) = self.views.get(&name) {
+                extend_tables(&mut self.inputs, view.inputs.iter().cloned());
+                return Ok(());
+            }
+            self.add_reference(&table.0, &name, TableRole::Input);
+            self.insert_input(name);
+        }
+        Ok(())
+    }
+
+    fn add_reference(&mut self, name: &[Ident], table: &DbTableMeta, role: TableRole) {
+        if !self.is_excluded(table) {
+            self.references.push(TableReference {
+                name: name.to_vec(),
+                table: table.clone(),
+                role,
+            });
+        }
+    }
+
+    fn insert_input(&mut self, table: DbTableMeta) {
+        if !self.is_excluded(&table) {
+            extend_tables(&mut self.inputs, [table]);
+        }
+    }
+
+    fn add_output(&mut self, output: &ObjectName, operation: Operation) -> Result<(), ParseError> {
+        let name = self.table_name(output)?;
+        if self.find_cte(&name).is_none() {
+            let name = self.resolve_table(name);
+            let temporary = temporary::is_session_table(&name);
+            self.add_reference(&output.0, &name, TableRole::Output);
+            self.insert_output(name, operation, temporary);
         }
+        Ok(())
     }
 
-    fn add_table_alias(&mut self, alias: &TableAlias) {
-        let name = DbTableMeta::new(alias.name.value.clone(), self);
-        self.aliases.insert(name);
+    fn add_temporary_output(
+        &mut self,
+        output: &ObjectName,
+        operation: Operation,
+    ) -> Result<(), ParseError> {
+        let name = self.table_name(output)?;
+        if self.find_cte(&name).is_none() {
+            let name = self.resolve_table(name);
+            self.add_reference(&output.0, &name, TableRole::Output);
+            self.insert_output(name, operation, true);
+        }
+        Ok(())
     }
 
-    fn add_ident_alias(&mut self, alias: &Ident) {
-        let name = DbTableMeta::new(alias.value.clone(), self);
-        self.aliases.insert(name);
+    fn insert_output(&mut self, table: DbTableMeta, operation: Operation, temporary: bool) {
+        if self.is_excluded(&table) {
+            return;
+        }
+        if temporary {
+            self.temporary.insert(table.clone());
+        }
+        self.operations
+            .insert(DatasetOperation::new(table.clone(), operation));
+        extend_tables(&mut self.outputs, [table]);
     }
 
-    fn add_input(&mut self, table: &String) {
-        let name = DbTableMeta::new(table.clone(), self);
-        if !self.aliases.contains(&name) {
-            self.inputs.insert(name);
+    // Renamed table is reported as input under its old name and as output under the new one,
+    // so that lineage leads from one to the other.
+    fn add_rename(&mut self, from: &ObjectName, to: &ObjectName) -> Result<(), ParseError> {
+        let old = self.table_name(from)?;
+        let old = self.resolve_table(old);
+        let new = self.table_name(to)?;
+        // Most databases keep renamed table in its schema, unless new name says otherwise.
+        // Snowflake, MySQL and Hive put it in the current one, like any other name.
+        let dialect = self.dialect.as_base();
+        let new = if dialect.is::<SnowflakeDialect>()
+            || dialect.is::<MySqlDialect>()
+            || dialect.is::<HiveDialect>()
+        {
+            self.resolve_table(new)
+        } else {
+            DbTableMeta {
+                server: new.server.or_else(|| old.server.clone()),
+                database: new.database.or_else(|| old.database.clone()),
+                schema: new.schema.or_else(|| old.schema.clone()),
+                ..new
+            }
+        };
+        if self.is_excluded(&old) || self.is_excluded(&new) {
+            return Ok(());
         }
+        self.add_reference(&from.0, &old, TableRole::Input);
+        self.insert_input(old.clone());
+        self.add_reference(&to.0, &new, TableRole::Output);
+        let temporary = temporary::is_session_table(&new);
+        self.insert_output(new.clone(), Operation::Rename, temporary);
+        self.renames.insert(TableRename { from: old, to: new });
+        Ok(())
     }
 
-    fn add_output(&mut self, output: &String) {
-        let name = DbTableMeta::new(output.clone(), self);
-        if !self.aliases.contains(&name) {
-            self.outputs.insert(name);
+    // Dropped view is forgotten, so that later statements read its name as a table. With
+    // `CASCADE`, views created in the script that read dropped table are dropped with it.
+    fn add_drop(&mut self, name: &ObjectName, cascade: bool) -> Result<(), ParseError> {
+        let table = self.table_name(name)?;
+        let table = self.resolve_table(table);
+        self.views.remove(&table);
+        if cascade {
+            let dependent: Vec<DbTableMeta> = self
+                .views
+                .iter()
+                .filter(|(_, view)| view.inputs.contains(&table))
+                .map(|(view, _)| view.clone())
+                .collect();
+            for view in dependent {
+                self.views.remove(&view);
+                self.insert_output(view, Operation::Drop, false);
+            }
         }
+        self.add_output(name, Operation::Drop)
+    }
+
+    fn add_cte(&mut self, alias: &TableAlias, columns: Option<Vec<OutputColumn>>) {
+        let name = DbTableMeta {
+            server: None,
+            database: None,
+            schema: None,
+            name: self.dialect.canonical_name(&alias.name),
+            partitions: vec![],
+            wildcard: false,
+            parts: vec![NamePart::from(&alias.name)],
+            original_name: alias.name.to_string(),
+        };
+        self.current_scope().ctes.insert(name, columns);
+    }
+
+    // Remembers what view reads, for later statements of the script. Has to be called
+    // after its query is parsed, when inputs of the statement are inputs of the view.
+    fn add_view(
+        &mut self,
+        name: &ObjectName,
+        columns: Vec<OutputColumn>,
+    ) -> Result<(), ParseError> {
+        let name = self.table_name(name)?;
+        let name = self.resolve_table(name);
+        let view = ViewDefinition {
+            columns,
+            inputs: self.inputs.iter().cloned().collect(),
+        };
+        self.views.insert(name, view);
+        Ok(())
+    }
+
+    fn add_relation(&mut self, relation: Relation) {
+        self.current_scope().relations.push(relation);
     }
+
+    fn add_column_lineage(
+        &mut self,
+        output: &ObjectName,
+        columns: &[OutputColumn],
+    ) -> Result<(), ParseError> {
+        let table = self.table_name(output)?;
+        if self.find_cte(&table).is_some() {
+            return Ok(());
+        }
+        let table = self.resolve_table(table);
+        if self.is_excluded(&table) {
+            return Ok(());
+        }
+        for column in columns {
+            let sources: Vec<ColumnMeta> = column
+                .sources
+                .iter()
+                .filter(|source| match &source.origin {
+                    Some(origin) => !self.is_excluded(origin),
+                    None => true,
+                })
+                .cloned()
+                .collect();
+            self.column_lineage
+                .entry(ColumnMeta::new(column.name.clone(), Some(table.clone())))
+                .or_default()
+                .extend(sources);
+        }
+        Ok(())
+    }
+
+    // Returns relation table name refers to: either CTE, or actual table.
+    fn get_relation(
+        &mut self,
+        table: &ObjectName,
+        alias: Option<&TableAlias>,
+    ) -> Result<Relation, ParseError> {
+        let name = self.table_name(table)?;
+        let alias_name = alias
+            .map(|a| a.name.value.clone())
+            .unwrap_or_else(|| name.name.clone());
+        Ok(match self.find_cte(&name) {
+            Some(Some(columns)) => Relation::derived(columns.clone(), Some(alias_name)),
+            Some(None) => Relation::recursive(alias_name),
+            None => {
+                let name = self.resolve_table(name);
+                if let Some(view) = self.views.get(&name) {
+                    return Ok(Relation::derived(view.columns.clone(), Some(alias_name)));
+                }
+                let columns = self.get_table_columns(&name);
+                Relation::table(name, columns, alias)
+            }
+        })
+    }
+
+    // Rewrites names of extracted tables with mapping caller provided. Tables that turn out
+    // to be the same dataset are merged.
+    fn apply_name_mapping(&mut self) {
+        let mapping = match self.name_mapping.clone() {
+            Some(mapping) => mapping,
+            None => return,
+        };
+        let map_column = |column: ColumnMeta| ColumnMeta {
+            origin: column.origin.map(|table| mapping.map(&table)),
+            ..column
+        };
+        let mut inputs = HashSet::new();
+        extend_tables(&mut inputs, self.inputs.drain().map(|t| mapping.map(&t)));
+        self.inputs = inputs;
+        let mut outputs = HashSet::new();
+        extend_tables(&mut outputs, self.outputs.drain().map(|t| mapping.map(&t)));
+        self.outputs = outputs;
+        self.temporary = self.temporary.drain().map(|t| mapping.map(&t)).collect();
+        self.operations = self
+            .operations
+            .drain()
+            .map(|op| DatasetOperation::new(mapping.map(&op.table), op.operation))
+            .collect();
+        let mut column_lineage: HashMap<ColumnMeta, HashSet<ColumnMeta>> = HashMap::new();
+        for (column, sources) in self.column_lineage.drain() {
+            column_lineage
+                .entry(map_column(column))
+                .or_default()
+                .extend(sources.into_iter().map(map_column));
+        }
+        self.column_lineage = column_lineage;
+        for reference in &mut self.references {
+            reference.table = mapping.map(&reference.table);
+        }
+        self.renames = self
+            .renames
+            .drain()
+            .map(|rename| TableRename {
+                from: mapping.map(&rename.from),
+                to: mapping.map(&rename.to),
+            })
+            .collect();
+    }
+
+    // Resolves column reference against relations of current scope, then of outer ones.
+    fn resolve_column(&self, ident: &[Ident]) -> Vec<ColumnMeta> {
+        let scopes: Vec<&[Relation]> = self
+            .scopes
+            .iter()
+            .rev()
+            .map(|scope| scope.relations.as_slice())
+            .filter(|relations| !relations.is_empty())
+            .collect();
+        resolve_column(&scopes, ident)
+    }
+
+    // Columns of the output table, used to name query columns when INSERT doesn't list them.
+    fn get_output_columns(
+        &mut self,
+        output: &ObjectName,
+    ) -> Result<Option<Vec<Ident>>, ParseError> {
+        let table = self.table_name(output)?;
+        let table = self.resolve_table(table);
+        Ok(self
+            .get_table_columns(&table)
+            .map(|columns| columns.into_iter().map(Ident::new).collect()))
+    }
+}
+
+/// Additional information, not present in SQL text itself, that lets parser extract
+/// more complete lineage.
+#[derive(Debug, Clone, Default)]
+pub struct ParseOptions {
+    // Schema assigned to tables which don't have it specified. Ignored if search path is set.
+    pub default_schema: Option<String>,
+    // Schemas in which tables without schema are looked for, in order, like Postgres
+    // `search_path`. Table is assigned to the first schema schema provider knows it in,
+    // or to the first schema if there's no provider.
+    pub search_path: Vec<String>,
+    // Database, also called project or catalog, assigned to tables which don't have it
+    // specified, so that they have fully qualified names. In MySQL and Hive, which don't
+    // have schemas, it's used as default schema instead.
+    pub default_database: Option<String>,
+    // Used to expand wildcards, to find to which table unqualified column belongs to,
+    // and to resolve fully qualified table names.
+    pub schema_provider: Option<Arc<dyn SchemaProvider>>,
+    // Instead of failing on the first error, skip statements that can't be parsed and parts
+    // of statements that aren't supported, and report them in SqlMeta::errors.
+    pub tolerant: bool,
+    // Report temporary tables as inputs and outputs of multi-statement script. By default,
+    // they are replaced by tables they were filled from.
+    pub keep_temporary_tables: bool,
+    // Report system tables of the dialect, like `information_schema` views, as inputs and
+    // outputs. By default, they are left out of lineage.
+    pub include_system_tables: bool,
+    // Tables left out of lineage, in addition to system ones.
+    pub exclude: Vec<TablePattern>,
+    // Rewrites names of extracted tables, so that the same dataset known under different
+    // names is reported under one.
+    pub name_mapping: Option<Arc<NameMapping>>,
 }
 
-#[pyclass]
-#[derive(Debug, Clone, PartialEq, Eq, Hash, Ord, PartialOrd)]
+// Identity of the table is its normalized name. Original spelling is kept only for reference,
+// so that the same table spelled differently is reported once.
+#[cfg_attr(feature = "python", pyo3::pyclass)]
+#[derive(Debug, Clone)]
 pub struct DbTableMeta {
-    #[pyo3(get)]
-    database: Option<String>,
-    #[pyo3(get)]
-    schema: Option<String>,
-    #[pyo3(get)]
-    name: String,
-    // ..columns
+    // Linked server, or instance, that SQL Server four-part names start with.
+    pub server: Option<String>,
+    pub database: Option<String>,
+    pub schema: Option<String>,
+    pub name: String,
+    // Partitions of the table query refers to, like `20220101` of BigQuery `events$20220101`.
+    // Empty if it refers to the whole table. Not part of identity of the table, like original
+    // name.
+    pub partitions: Vec<String>,
+    // Whether name is a pattern matching many tables, like BigQuery wildcard table `events_*`.
+    // Pattern is kept as the name.
+    pub wildcard: bool,
+    // Parts of the name as written in SQL, with their quoting. Tables resolved to the same
+    // name can have different parts, if they were qualified differently.
+    pub parts: Vec<NamePart>,
+    // Name as it was written in SQL, before delimiters were stripped and case was folded.
+    pub original_name: String,
 }
 
 impl DbTableMeta {
-    fn new(name: String, context: &mut Context) -> Self {
-        let mut split = name
-            .split('.')
-            .map(|x| {
-                context
-                    .dialect
-                    .canonical_name(String::from(x))
-                    .unwrap_or(String::from(x))
-            })
-            .collect::<Vec<String>>();
-        split.reverse();
-        let table_name = match split.get(0) {
-            Some(x) => x,
-            None => &name,
+    // Builds table name from parts of the name as parsed. Table is identified by at most
+    // three parts, or four in SQL Server, so longer names are rejected rather than truncated.
+    // BigQuery's INFORMATION_SCHEMA views have four parts too, with project as the first one.
+    // Names not read from SQL, like in schema, may have four parts in any dialect.
+    fn from_idents(
+        idents: &[Ident],
+        original_name: String,
+        dialect: &dyn CanonicalDialect,
+        allow_server: bool,
+    ) -> Result<Self, ParseError> {
+        let bigquery = dialect.as_base().is::<BigQueryDialect>();
+        let mut idents = if bigquery {
+            bigquery::split_quoted_path(idents)
+        } else {
+            idents.to_vec()
         };
-        DbTableMeta {
-            database: split.get(2).cloned(),
-            schema: split.get(1).cloned().or(context.default_schema.clone()),
-            name: table_name.clone(),
+        let name_parts = idents.iter().map(NamePart::from).collect();
+        let partitions = match idents.last_mut() {
+            Some(last) if bigquery => bigquery::split_partition(last).into_iter().collect(),
+            _ => vec![],
+        };
+        let wildcard = bigquery && matches!(idents.last(), Some(last) if last.value.ends_with('*'));
+        let with_server = allow_server
+            || dialect.as_base().is::<MsSqlDialect>()
+            || (bigquery && bigquery::is_information_schema_view(&idents));
+        let mut parts = idents.iter().map(|ident| dialect.canonical_name(ident));
+        match (
+            parts.next_back(),
+            parts.next_back(),
+            parts.next_back(),
+            parts.next_back(),
+            parts.next_back(),
+        ) {
+            (Some(name), schema, database, server, None) if with_server || server.is_none() => {
+                Ok(DbTableMeta {
+                    server,
+                    database,
+                    schema,
+                    name,
+                    partitions,
+                    wildcard,
+                    parts: name_parts,
+                    original_name,
+                })
+            }
+            _ => Err(ParseError::Unsupported {
+                node: String::from("ObjectName"),
+                message: format!(
+                    "Table name {} has more than {} parts",
+                    original_name,
+                    if with_server { "four" } else { "three" }
+                ),
+            }),
         }
     }
+
+    // Reads table name from text, like `db.schema."Orders"`. Text that isn't a name of at most
+    // four parts in the dialect is split on dots, or kept whole if it has more parts.
+    fn parse(name: String, dialect: &dyn CanonicalDialect) -> Self {
+        let idents = tokens::parse_object_name(dialect.as_base(), &name)
+            .unwrap_or_else(|| name.split('.').map(Ident::new).collect());
+        DbTableMeta::from_idents(&idents, name.clone(), dialect, true).unwrap_or(DbTableMeta {
+            server: None,
+            database: None,
+            schema: None,
+            name: name.clone(),
+            partitions: vec![],
+            wildcard: false,
+            parts: vec![NamePart {
+                value: name.clone(),
+                quote_style: None,
+            }],
+            original_name: name,
+        })
+    }
+
+    // The same table referenced in several places refers to partitions of all of them,
+    // or to the whole table if any of them does.
+    fn merge_partitions(&mut self, other: &DbTableMeta) {
+        if self.partitions.is_empty() || other.partitions.is_empty() {
+            self.partitions.clear();
+        } else {
+            self.partitions.extend(other.partitions.iter().cloned());
+            self.partitions.sort();
+            self.partitions.dedup();
+        }
+    }
+
+    fn key(&self) -> (&Option<String>, &Option<String>, &Option<String>, &String) {
+        (&self.server, &self.database, &self.schema, &self.name)
+    }
+}
+
+// Adds tables to the set, merging partitions of ones that are in it already.
+pub(crate) fn extend_tables<I: IntoIterator<Item = DbTableMeta>>(
+    tables: &mut HashSet<DbTableMeta>,
+    new: I,
+) {
+    for table in new {
+        let table = match tables.take(&table) {
+            Some(mut existing) => {
+                existing.merge_partitions(&table);
+                existing
+            }
+            None => table,
+        };
+        tables.insert(table);
+    }
+}
+
+impl PartialEq for DbTableMeta {
+    fn eq(&self, other: &Self) -> bool {
+        self.key() == other.key()
+    }
+}
+
+impl Eq for DbTableMeta {}
+
+impl Hash for DbTableMeta {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.key().hash(state)
+    }
+}
+
+impl PartialOrd for DbTableMeta {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for DbTableMeta {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.key().cmp(&other.key())
+    }
 }
 
-#[pymethods]
 impl DbTableMeta {
-    #[new]
-    pub fn py_new(name: String) -> Self {
-        DbTableMeta::new(name, &mut Context::default())
+    pub fn new_default_dialect(name: String) -> Self {
+        DbTableMeta::parse(name, &GenericDialect)
+    }
+
+    // Qualified name, with parts quoted where dialect requires it to read the same name back.
+    pub fn quoted_name(&self, dialect: &dyn CanonicalDialect) -> String {
+        [&self.server, &self.database, &self.schema]
+            .into_iter()
+            .flatten()
+            .chain(std::iter::once(&self.name))
+            .map(|part| identifier::render(dialect, part))
+            .collect::<Vec<String>>()
+            .join(".")
     }
 
-    #[getter(qualified_name)]
     pub fn qualified_name(&self) -> String {
         format!(
-            "{}{}{}",
+            "{}{}{}{}",
+            self.server
+                .as_ref()
+                .map(|x| format!("{}.", x))
+                .unwrap_or("".to_string()),
             self.database
                 .as_ref()
                 .map(|x| format!("{}.", x))
@@ -179,112 +799,281 @@ impl DbTableMeta {
             self.name
         )
     }
-
-    fn __richcmp__(&self, other: &Self, op: CompareOp) -> PyResult<bool> {
-        match op {
-            CompareOp::Eq => Ok(self.qualified_name() == other.qualified_name()),
-            CompareOp::Ne => Ok(self.qualified_name() != other.qualified_name()),
-            _ => Err(PyTypeError::new_err(format!(
-                "can't use operator {op:?} on DbTableMeta"
-            ))),
-        }
-    }
-
-    fn __repr__(&self) -> String {
-        self.qualified_name()
-    }
-
-    fn __str__(&self) -> String {
-        self.__repr__()
-    }
-
-    fn __hash__(&self) -> isize {
-        let mut hasher = DefaultHasher::new();
-        self.hash(&mut hasher);
-        hasher.finish() as isize
-    }
 }
 
-#[pyclass]
+#[cfg_attr(feature = "python", pyo3::pyclass)]
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct SqlMeta {
-    #[pyo3(get)]
     pub in_tables: Vec<DbTableMeta>,
-    #[pyo3(get)]
     pub out_tables: Vec<DbTableMeta>,
+    pub column_lineage: Vec<ColumnLineage>,
+    // Operations applied to each of out_tables.
+    pub operations: Vec<DatasetOperation>,
+    // Every place in SQL text where one of in_tables or out_tables is referenced.
+    pub occurrences: Vec<TableOccurrence>,
+    // Tables renamed, which are also among in_tables under old name and out_tables under new.
+    pub renames: Vec<TableRename>,
+    // Operations that changed lifecycle state of out_tables, in order of statements.
+    pub lifecycle_changes: Vec<LifecycleChange>,
+    // Problems skipped in tolerant mode. Always empty otherwise.
+    pub errors: Vec<ExtractionError>,
 }
 
-#[pymethods]
 impl SqlMeta {
-    fn __repr__(&self) -> String {
-        format!(
-            "{{\"in_tables\": {:?}, \"out_tables\": {:?} }}",
-            self.in_tables, self.out_tables
-        )
-    }
-
-    fn __str__(&self) -> String {
-        self.__repr__()
-    }
-}
-
-impl SqlMeta {
-    fn new(inputs: Vec<DbTableMeta>, outputs: Vec<DbTableMeta>) -> Self {
+    #[allow(clippy::too_many_arguments)]
+    fn new(
+        inputs: Vec<DbTableMeta>,
+        outputs: Vec<DbTableMeta>,
+        column_lineage: HashMap<ColumnMeta, HashSet<ColumnMeta>>,
+        operations: HashSet<DatasetOperation>,
+        mut occurrences: Vec<TableOccurrence>,
+        renames: HashSet<TableRename>,
+        mut lifecycle_changes: Vec<LifecycleChange>,
+        errors: Vec<ExtractionError>,
+    ) -> Self {
         let mut inputs: Vec<DbTableMeta> = inputs.clone();
         let mut outputs: Vec<DbTableMeta> = outputs.clone();
         inputs.sort();
         outputs.sort();
+        let mut column_lineage: Vec<ColumnLineage> = column_lineage
+            .into_iter()
+            .map(|(descendant, lineage)| {
+                let mut lineage: Vec<ColumnMeta> = lineage.into_iter().collect();
+                lineage.sort();
+                ColumnLineage {
+                    descendant,
+                    lineage,
+                }
+            })
+            .collect();
+        column_lineage.sort();
+        let mut operations: Vec<DatasetOperation> = operations.into_iter().collect();
+        operations.sort();
+        occurrences.sort();
+        let mut renames: Vec<TableRename> = renames.into_iter().collect();
+        renames.sort();
+        lifecycle_changes.sort();
         SqlMeta {
             in_tables: inputs,
             out_tables: outputs,
+            column_lineage,
+            operations,
+            occurrences,
+            renames,
+            lifecycle_changes,
+            errors,
+        }
+    }
+
+    // Combines lineage of multiple statements, keeping errors in order.
+    fn merge<I: IntoIterator<Item = SqlMeta>>(metas: I) -> Self {
+        let mut inputs: HashSet<DbTableMeta> = HashSet::new();
+        let mut outputs: HashSet<DbTableMeta> = HashSet::new();
+        let mut column_lineage: HashMap<ColumnMeta, HashSet<ColumnMeta>> = HashMap::new();
+        let mut operations: HashSet<DatasetOperation> = HashSet::new();
+        let mut occurrences: Vec<TableOccurrence> = vec![];
+        let mut renames: HashSet<TableRename> = HashSet::new();
+        let mut lifecycle_changes: Vec<LifecycleChange> = vec![];
+        let mut errors: Vec<ExtractionError> = vec![];
+        for meta in metas {
+            extend_tables(&mut inputs, meta.in_tables);
+            extend_tables(&mut outputs, meta.out_tables);
+            for lineage in meta.column_lineage {
+                column_lineage
+                    .entry(lineage.descendant)
+                    .or_default()
+                    .extend(lineage.lineage);
+            }
+            operations.extend(meta.operations);
+            occurrences.extend(meta.occurrences);
+            renames.extend(meta.renames);
+            lifecycle_changes.extend(meta.lifecycle_changes);
+            errors.extend(meta.errors);
+        }
+        SqlMeta::new(
+            inputs.into_iter().collect(),
+            outputs.into_iter().collect(),
+            column_lineage,
+            operations,
+            occurrences,
+            renames,
+            lifecycle_changes,
+            errors,
+        )
+    }
+
+    /// Builds ColumnLineageDatasetFacet for every output table that has column lineage.
+    /// All input datasets are assumed to be in the same `namespace`.
+    pub fn column_lineage_facets(
+        &self,
+        namespace: &str,
+        producer: &str,
+    ) -> Vec<(DbTableMeta, ColumnLineageDatasetFacet)> {
+        facet::column_lineage_facets(&self.column_lineage, namespace, producer)
+    }
+
+    /// Builds LifecycleStateChangeDatasetFacet for every output table that was created,
+    /// overwritten, truncated, dropped or renamed. Renamed table's previous name is assumed
+    /// to be in the same `namespace`.
+    pub fn lifecycle_state_change_facets(
+        &self,
+        namespace: &str,
+        producer: &str,
+    ) -> Vec<(DbTableMeta, LifecycleStateChangeDatasetFacet)> {
+        facet::lifecycle_state_change_facets(
+            &self.lifecycle_changes,
+            &self.renames,
+            namespace,
+            producer,
+        )
+    }
+}
+
+/// Lineage of a single statement.
+#[cfg_attr(feature = "python", pyo3::pyclass)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct StatementMeta {
+    // Position of the statement, counting from the first statement of the first SQL text.
+    pub index: usize,
+    // Source text of the statement.
+    pub statement: String,
+    pub sql_meta: SqlMeta,
+    // Outputs of this statement that are temporary tables.
+    pub temporary_tables: Vec<DbTableMeta>,
+}
+
+impl StatementMeta {
+    fn new(
+        index: usize,
+        statement: String,
+        mut context: Context,
+        errors: Vec<ParseError>,
+        tokens: &[LocatedToken],
+    ) -> Self {
+        context.apply_name_mapping();
+        let occurrences = occurrence::locate(index, &context.references, tokens);
+        let errors = context
+            .errors
+            .into_iter()
+            .chain(errors)
+            .map(|reason| ExtractionError {
+                index,
+                statement: statement.clone(),
+                reason,
+            })
+            .collect();
+        let mut temporary_tables: Vec<DbTableMeta> = context.temporary.into_iter().collect();
+        temporary_tables.sort();
+        let lifecycle_changes = context
+            .operations
+            .iter()
+            .filter(|o| o.operation.lifecycle_state_change().is_some())
+            .map(|o| LifecycleChange {
+                statement_index: index,
+                table: o.table.clone(),
+                operation: o.operation,
+            })
+            .collect();
+        StatementMeta {
+            index,
+            temporary_tables,
+            sql_meta: SqlMeta::new(
+                context.inputs.into_iter().collect(),
+                context.outputs.into_iter().collect(),
+                context.column_lineage,
+                context.operations,
+                occurrences,
+                context.renames,
+                lifecycle_changes,
+                errors,
+            ),
+            statement,
         }
     }
+
+    /// Pairs of (input, output) tables: data of each output may come from each input
+    /// of the same statement.
+    pub fn table_edges(&self) -> Vec<(DbTableMeta, DbTableMeta)> {
+        let meta = &self.sql_meta;
+        meta.in_tables
+            .iter()
+            .flat_map(|input| {
+                meta.out_tables
+                    .iter()
+                    .map(move |output| (input.clone(), output.clone()))
+            })
+            .collect()
+    }
 }
 
-fn parse_with(with: &With, context: &mut Context) -> Result<(), String> {
+fn parse_with(with: &With, context: &mut Context) -> Result<(), ParseError> {
     for cte in &with.cte_tables {
-        context.add_table_alias(&cte.alias);
-        parse_query(&cte.query, context)?;
+        // Only recursive CTE can refer to itself. Otherwise, its name in its own body
+        // refers to table or to CTE from outer query.
+        if with.recursive {
+            context.add_cte(&cte.alias, None);
+        }
+        let mut columns = parse_query(&cte.query, context)?;
+        rename_columns(&mut columns, &cte.alias.columns);
+        context.add_cte(&cte.alias, Some(columns));
     }
     Ok(())
 }
 
-fn parse_table_factor(table: &TableFactor, context: &mut Context) -> Result<(), String> {
+fn parse_table_factor(table: &TableFactor, context: &mut Context) -> Result<Relation, ParseError> {
     match table {
-        TableFactor::Table { name, .. } => {
-            context.add_input(&name.to_string());
-            Ok(())
+        TableFactor::Table {
+            name, alias, args, ..
+        } => {
+            if let Some(args) = args {
+                let relation = remote::parse_remote_source(name, args, alias.as_ref(), context)?;
+                if let Some(relation) = relation {
+                    return Ok(relation);
+                }
+            }
+            context.add_input(name)?;
+            context.get_relation(name, alias.as_ref())
         }
         TableFactor::Derived {
             lateral: _,
             subquery,
             alias,
         } => {
-            parse_query(subquery, context)?;
+            let mut columns = parse_query(subquery, context)?;
             if let Some(a) = alias {
-                context.add_table_alias(a);
+                rename_columns(&mut columns, &a.columns);
             }
-            Ok(())
+            Ok(Relation::derived(
+                columns,
+                alias.as_ref().map(|a| a.name.value.clone()),
+            ))
+        }
+        _ => {
+            context.skip_unsupported(ParseError::unsupported(
+                "TableFactor",
+                table,
+                format!("TableFactor other than table or subquery not implemented: {table}"),
+            ))?;
+            Ok(Relation::derived(vec![], None))
         }
-        _ => Err(format!(
-            "TableFactor other than table or subquery not implemented: {table}"
-        )),
     }
 }
 
-fn get_table_name_from_table_factor(table: &TableFactor) -> Result<String, String> {
+fn get_table_name_from_table_factor(table: &TableFactor) -> Result<&ObjectName, ParseError> {
     if let TableFactor::Table { name, .. } = table {
-        Ok(name.to_string())
+        Ok(name)
     } else {
-        Err(format!(
-            "Name can be got only from simple table, got {table}"
+        Err(ParseError::unsupported(
+            "TableFactor",
+            table,
+            format!("Name can be got only from simple table, got {table}"),
         ))
     }
 }
 
 /// Process expression in case where we want to extract lineage (for eg. in subqueries)
 /// This means most enum types are untouched, where in other contexts they'd be processed.
-fn parse_expr(expr: &Expr, context: &mut Context) -> Result<(), String> {
+fn parse_expr(expr: &Expr, context: &mut Context) -> Result<(), ParseError> {
     match expr {
         Expr::Subquery(query) => {
             parse_query(query, context)?;
@@ -296,6 +1085,12 @@ fn parse_expr(expr: &Expr, context: &mut Context) -> Result<(), String> {
         } => {
             parse_query(subquery, context)?;
         }
+        Expr::Exists { subquery, .. } => {
+            parse_query(subquery, context)?;
+        }
+        Expr::Nested(expr) => {
+            parse_expr(expr, context)?;
+        }
         Expr::BinaryOp { left, op: _, right } => {
             parse_expr(left, context)?;
             parse_expr(right, context)?;
@@ -318,100 +1113,256 @@ fn parse_expr(expr: &Expr, context: &mut Context) -> Result<(), String> {
     Ok(())
 }
 
-fn parse_select(select: &Select, context: &mut Context) -> Result<(), String> {
-    for projection in &select.projection {
-        match projection {
-            SelectItem::UnnamedExpr(expr) => {
-                parse_expr(&expr, context)?;
-            }
-            SelectItem::ExprWithAlias { expr, alias } => {
-                parse_expr(&expr, context)?;
-                context.add_ident_alias(&alias);
-            }
-            _ => {}
+/// Process expression that produces column value, like one in SELECT list or in assignment.
+/// Returns input columns that value is computed from, and extracts lineage from subqueries.
+fn parse_column_expr(expr: &Expr, context: &mut Context) -> Result<Vec<ColumnMeta>, ParseError> {
+    let refs = ExprRefs::collect(expr);
+    let mut sources = vec![];
+    for query in refs.queries {
+        for column in parse_query(query, context)? {
+            sources.extend(column.sources);
         }
     }
-
-    if let Some(into) = &select.into {
-        context.add_output(&into.name.to_string())
+    for column in refs.columns {
+        sources.extend(context.resolve_column(column));
     }
+    Ok(sources)
+}
 
-    for table in &select.from {
-        parse_table_factor(&table.relation, context)?;
-        for join in &table.joins {
-            parse_table_factor(&join.relation, context)?;
+fn parse_select(select: &Select, context: &mut Context) -> Result<Vec<OutputColumn>, ParseError> {
+    context.in_scope(|context| {
+        // Relations are added one by one, so that lateral subqueries see ones preceding them.
+        for table in &select.from {
+            let relation = parse_table_factor(&table.relation, context)?;
+            context.add_relation(relation);
+            for join in &table.joins {
+                let relation = parse_table_factor(&join.relation, context)?;
+                context.add_relation(relation);
+            }
         }
-    }
-    Ok(())
+
+        let mut columns = vec![];
+        for projection in &select.projection {
+            match projection {
+                SelectItem::UnnamedExpr(expr) => {
+                    let sources = parse_column_expr(expr, context)?;
+                    columns.push(OutputColumn::new(expr_name(expr), sources));
+                }
+                SelectItem::ExprWithAlias { expr, alias } => {
+                    let sources = parse_column_expr(expr, context)?;
+                    columns.push(OutputColumn::new(alias.value.clone(), sources));
+                }
+                SelectItem::Wildcard => {
+                    for relation in &context.current_scope().relations {
+                        columns.extend(relation.columns().unwrap_or_default());
+                    }
+                }
+                SelectItem::QualifiedWildcard(name) => {
+                    let relations = &context.current_scope().relations;
+                    if let Some(relation) = relations.iter().find(|r| r.matches(&name.0)) {
+                        columns.extend(relation.columns().unwrap_or_default());
+                    }
+                }
+            }
+        }
+
+        if let Some(into) = &select.into {
+            if into.temporary {
+                context.add_temporary_output(&into.name, Operation::Create)?;
+            } else {
+                context.add_output(&into.name, Operation::Create)?;
+            }
+            context.add_column_lineage(&into.name, &columns)?;
+        }
+        Ok(columns)
+    })
 }
 
-fn parse_setexpr(setexpr: &SetExpr, context: &mut Context) -> Result<(), String> {
+fn parse_setexpr(
+    setexpr: &SetExpr,
+    context: &mut Context,
+) -> Result<Vec<OutputColumn>, ParseError> {
     match setexpr {
-        SetExpr::Select(select) => parse_select(select, context)?,
-        SetExpr::Values(_) => (),
-        SetExpr::Insert(stmt) => parse_stmt(stmt, context)?,
-        SetExpr::Query(q) => parse_query(q, context)?,
+        SetExpr::Select(select) => parse_select(select, context),
+        SetExpr::Values(_) => Ok(vec![]),
+        SetExpr::Insert(stmt) => {
+            parse_stmt(stmt, context)?;
+            Ok(vec![])
+        }
+        SetExpr::Query(q) => parse_query(q, context),
         SetExpr::SetOperation {
             op: _,
             all: _,
             left,
             right,
         } => {
-            parse_setexpr(left, context)?;
-            parse_setexpr(right, context)?;
+            // Columns of set operation are matched by position, and named after the left side.
+            let mut columns = parse_setexpr(left, context)?;
+            let right_columns = parse_setexpr(right, context)?;
+            for (column, other) in columns.iter_mut().zip(right_columns.iter()) {
+                column.merge(other);
+            }
+            Ok(columns)
         }
-    };
-    Ok(())
+    }
 }
 
-fn parse_query(query: &Query, context: &mut Context) -> Result<(), String> {
-    match &query.with {
-        Some(with) => parse_with(with, context)?,
-        None => (),
-    };
-
-    parse_setexpr(&query.body, context)?;
-    Ok(())
+fn parse_query(query: &Query, context: &mut Context) -> Result<Vec<OutputColumn>, ParseError> {
+    context.in_scope(|context| {
+        if let Some(with) = &query.with {
+            parse_with(with, context)?;
+        }
+        parse_setexpr(&query.body, context)
+    })
 }
 
-fn parse_stmt(stmt: &Statement, context: &mut Context) -> Result<(), String> {
+fn parse_stmt(stmt: &Statement, context: &mut Context) -> Result<(), ParseError> {
     match stmt {
         Statement::Query(query) => {
             parse_query(query, context)?;
             Ok(())
         }
         Statement::Insert {
-            table_name, source, ..
+            table_name,
+            columns,
+            source,
+            overwrite,
+            on,
+            ..
         } => {
-            parse_query(source, context)?;
-            context.add_output(&table_name.to_string());
+            let mut query_columns = parse_query(source, context)?;
+            if columns.is_empty() {
+                if let Some(target_columns) = context.get_output_columns(table_name)? {
+                    rename_columns(&mut query_columns, &target_columns);
+                }
+            } else {
+                rename_columns(&mut query_columns, columns);
+            }
+            let operation = if *overwrite {
+                Operation::Overwrite
+            } else if on.is_some() {
+                Operation::Upsert
+            } else {
+                Operation::Append
+            };
+            context.add_output(table_name, operation)?;
+            context.add_column_lineage(table_name, &query_columns)?;
             Ok(())
         }
-        Statement::Merge { table, source, .. } => {
+        Statement::Merge {
+            table,
+            source,
+            on,
+            clauses,
+            ..
+        } => {
             let table_name = get_table_name_from_table_factor(table)?;
-            context.add_output(&table_name);
-            parse_table_factor(source, context)?;
-            Ok(())
+            context.add_output(table_name, Operation::Upsert)?;
+            let target_alias = match table {
+                TableFactor::Table { alias, .. } => alias.as_ref(),
+                _ => None,
+            };
+            let columns = context.in_scope(|context| {
+                let target = context.get_relation(table_name, target_alias)?;
+                context.add_relation(target);
+                let source = parse_table_factor(source, context)?;
+                context.add_relation(source);
+                parse_expr(on, context)?;
+
+                let mut columns = vec![];
+                for clause in clauses {
+                    match clause {
+                        MergeClause::MatchedUpdate {
+                            predicate,
+                            assignments,
+                        } => {
+                            if let Some(predicate) = predicate {
+                                parse_expr(predicate, context)?;
+                            }
+                            for assignment in assignments {
+                                if let Some(id) = assignment.id.last() {
+                                    let sources = parse_column_expr(&assignment.value, context)?;
+                                    columns.push(OutputColumn::new(id.value.clone(), sources));
+                                }
+                            }
+                        }
+                        MergeClause::NotMatched {
+                            predicate,
+                            columns: names,
+                            values,
+                        } => {
+                            if let Some(predicate) = predicate {
+                                parse_expr(predicate, context)?;
+                            }
+                            for row in &values.0 {
+                                for (name, expr) in names.iter().zip(row.iter()) {
+                                    let sources = parse_column_expr(expr, context)?;
+                                    columns.push(OutputColumn::new(name.value.clone(), sources));
+                                }
+                            }
+                        }
+                        MergeClause::MatchedDelete(predicate) => {
+                            if let Some(predicate) = predicate {
+                                parse_expr(predicate, context)?;
+                            }
+                        }
+                    }
+                }
+                Ok(columns)
+            })?;
+            context.add_column_lineage(table_name, &columns)
+        }
+        Statement::CreateView {
+            name,
+            columns,
+            query,
+            or_replace,
+            ..
+        } => {
+            let mut query_columns = parse_query(query, context)?;
+            rename_columns(&mut query_columns, columns);
+            context.add_column_lineage(name, &query_columns)?;
+            context.add_view(name, query_columns)?;
+            let operation = if *or_replace {
+                Operation::CreateOrReplace
+            } else {
+                Operation::Create
+            };
+            context.add_output(name, operation)
         }
         Statement::CreateTable {
             name,
+            columns,
             query,
             like,
             clone,
+            temporary,
+            or_replace,
             ..
         } => {
             if let Some(boxed_query) = query {
-                parse_query(boxed_query.as_ref(), context)?;
+                let mut query_columns = parse_query(boxed_query.as_ref(), context)?;
+                let names: Vec<Ident> = columns.iter().map(|c| c.name.clone()).collect();
+                rename_columns(&mut query_columns, &names);
+                context.add_column_lineage(name, &query_columns)?;
             }
             if let Some(like_table) = like {
-                context.add_input(&like_table.to_string());
+                context.add_input(like_table)?;
             }
             if let Some(clone) = clone {
-                context.add_input(&clone.to_string());
+                context.add_input(clone)?;
             }
 
-            context.add_output(&name.to_string());
-            Ok(())
+            let operation = if *or_replace {
+                Operation::CreateOrReplace
+            } else {
+                Operation::Create
+            };
+            if *temporary {
+                context.add_temporary_output(name, operation)
+            } else {
+                context.add_output(name, operation)
+            }
         }
         Statement::Update {
             table,
@@ -420,18 +1371,37 @@ fn parse_stmt(stmt: &Statement, context: &mut Context) -> Result<(), String> {
             selection,
         } => {
             let name = get_table_name_from_table_factor(&table.relation)?;
-            context.add_output(&name);
+            context.in_scope(|context| {
+                if let Some(src) = from {
+                    let relation = parse_table_factor(&src.relation, context)?;
+                    context.add_relation(relation);
+                    for join in &src.joins {
+                        let relation = parse_table_factor(&join.relation, context)?;
+                        context.add_relation(relation);
+                    }
+                }
+                // Target of UPDATE ... FROM can be an alias of table from FROM clause.
+                let aliased = match name.0.as_slice() {
+                    [alias] => context
+                        .current_scope()
+                        .relations
+                        .iter()
+                        .find_map(|r| r.aliased_table(&alias.value)),
+                    _ => None,
+                };
+                match aliased {
+                    Some(table) => {
+                        let temporary = temporary::is_session_table(&table);
+                        context.insert_output(table, Operation::Update, temporary)
+                    }
+                    None => context.add_output(name, Operation::Update)?,
+                }
 
-            if let Some(src) = from {
-                parse_table_factor(&src.relation, context)?;
-                for join in &src.joins {
-                    parse_table_factor(&join.relation, context)?;
+                if let Some(expr) = selection {
+                    parse_expr(expr, context)?;
                 }
-            }
-            if let Some(expr) = selection {
-                parse_expr(expr, context)?;
-            }
-            Ok(())
+                Ok(())
+            })
         }
         Statement::Delete {
             table_name,
@@ -439,7 +1409,7 @@ fn parse_stmt(stmt: &Statement, context: &mut Context) -> Result<(), String> {
             selection,
         } => {
             let table_name = get_table_name_from_table_factor(table_name)?;
-            context.add_output(&table_name.to_string());
+            context.add_output(table_name, Operation::Delete)?;
 
             if let Some(using) = using {
                 parse_table_factor(using, context)?;
@@ -450,6 +1420,18 @@ fn parse_stmt(stmt: &Statement, context: &mut Context) -> Result<(), String> {
             }
             Ok(())
         }
+        Statement::AlterTable {
+            name,
+            operation: AlterTableOperation::RenameTable { table_name },
+        } => context.add_rename(name, table_name),
+        Statement::Drop {
+            object_type: ObjectType::Table | ObjectType::View,
+            names,
+            cascade,
+            ..
+        } => names
+            .iter()
+            .try_for_each(|name| context.add_drop(name, *cascade)),
         _ => Ok(()),
     }
 }
@@ -462,6 +1444,8 @@ pub fn get_dialect(name: &str) -> Arc<dyn CanonicalDialect> {
         "postgresql" => Arc::new(PostgreSqlDialect {}),
         "redshift" => Arc::new(RedshiftSqlDialect {}),
         "hive" => Arc::new(HiveDialect {}),
+        // Databricks SQL is Spark SQL, which follows Hive syntax.
+        "databricks" => Arc::new(HiveDialect {}),
         "mysql" => Arc::new(MySqlDialect {}),
         "mssql" => Arc::new(MsSqlDialect {}),
         "sqlite" => Arc::new(SQLiteDialect {}),
@@ -483,69 +1467,106 @@ pub fn parse_multiple_statements(
     sql: Vec<&str>,
     dialect: Arc<dyn CanonicalDialect>,
     default_schema: Option<&str>,
-) -> Result<SqlMeta, String> {
-    let mut inputs: HashSet<DbTableMeta> = HashSet::new();
-    let mut outputs: HashSet<DbTableMeta> = HashSet::new();
-
-    for statement in sql {
-        let ast = match Parser::parse_sql(dialect.as_base(), statement) {
-            Ok(k) => k,
-            Err(e) => return Err(e.to_string()),
-        };
+) -> Result<SqlMeta, ParseError> {
+    let options = ParseOptions {
+        default_schema: default_schema.map(String::from),
+        ..ParseOptions::default()
+    };
+    parse_multiple_statements_with_options(sql, dialect, &options)
+}
 
-        if ast.is_empty() {
-            return Err(String::from("Empty statement list"));
-        }
+pub fn parse_multiple_statements_with_options(
+    sql: Vec<&str>,
+    dialect: Arc<dyn CanonicalDialect>,
+    options: &ParseOptions,
+) -> Result<SqlMeta, ParseError> {
+    let statements = parse_statements_with_options(sql, dialect, options)?;
+    if options.keep_temporary_tables {
+        Ok(SqlMeta::merge(statements.into_iter().map(|s| s.sql_meta)))
+    } else {
+        Ok(temporary::merge_without_temporary(statements))
+    }
+}
 
-        for stmt in ast {
-            let mut context = Context::new(dialect.clone(), default_schema);
-            parse_stmt(&stmt, &mut context)?;
-            for input in context.inputs.iter() {
-                inputs.insert(input.clone());
+/// Like `parse_multiple_statements_with_options`, but returns lineage of each statement
+/// separately, in order in which they appear in `sql`.
+pub fn parse_statements_with_options(
+    sql: Vec<&str>,
+    dialect: Arc<dyn CanonicalDialect>,
+    options: &ParseOptions,
+) -> Result<Vec<StatementMeta>, ParseError> {
+    let mut result: Vec<StatementMeta> = vec![];
+    let mut session = Session::new(dialect.as_ref(), options);
+
+    for text in sql {
+        let statements = match tokens::tokenize(dialect.as_base(), text) {
+            Ok(tokens) => match tokens::split_statements(tokens) {
+                statements if statements.is_empty() => Err(ParseError::EmptyInput),
+                statements => Ok(statements),
+            },
+            Err(e) => Err(e),
+        };
+        let statements = match statements {
+            Ok(statements) => statements,
+            Err(e) if options.tolerant => {
+                result.push(StatementMeta::new(
+                    result.len(),
+                    String::from(text),
+                    Context::new(dialect.clone(), options, &session),
+                    vec![e],
+                    &[],
+                ));
+                continue;
             }
-            for output in context.outputs.iter() {
-                outputs.insert(output.clone());
+            Err(e) => return Err(e),
+        };
+
+        for statement_tokens in statements {
+            let mut context = Context::new(dialect.clone(), options, &session);
+            let mut errors = vec![];
+            if !session.apply(dialect.as_ref(), &statement_tokens) {
+                let parsed = if let Some(renames) =
+                    rename::parse_rename(dialect.as_base(), &statement_tokens)
+                {
+                    renames
+                        .iter()
+                        .try_for_each(|(from, to)| context.add_rename(from, to))
+                } else if let Some(changes) = lifecycle::parse_lifecycle(&statement_tokens) {
+                    changes
+                        .iter()
+                        .try_for_each(|(name, operation)| context.add_output(name, *operation))
+                } else {
+                    tokens::parse_statement(dialect.as_base(), &statement_tokens)
+                        .and_then(|stmt| parse_stmt(&stmt, &mut context))
+                };
+                match parsed {
+                    Ok(()) => {}
+                    Err(e) if options.tolerant => errors.push(e),
+                    Err(e) => return Err(e),
+                }
             }
+            session.views = std::mem::take(&mut context.views);
+            let statement = tokens::statement_text(text, &statement_tokens);
+            result.push(StatementMeta::new(
+                result.len(),
+                String::from(statement),
+                context,
+                errors,
+                &statement_tokens,
+            ));
         }
     }
-    Ok(SqlMeta::new(
-        inputs.into_iter().collect(),
-        outputs.into_iter().collect(),
-    ))
+    Ok(result)
 }
 
 pub fn parse_sql(
     sql: &str,
     dialect: Arc<dyn CanonicalDialect>,
     default_schema: Option<&str>,
-) -> Result<SqlMeta, String> {
+) -> Result<SqlMeta, ParseError> {
     parse_multiple_statements(vec![sql], dialect, default_schema)
 }
 
-// Parses SQL.
-#[pyfunction]
-fn parse(sql: Vec<&str>, dialect: Option<&str>, default_schema: Option<&str>) -> PyResult<SqlMeta> {
-    match parse_multiple_statements(sql, get_generic_dialect(dialect), default_schema) {
-        Ok(ok) => Ok(ok),
-        Err(err) => Err(PyRuntimeError::new_err(err)),
-    }
-}
-
-#[pyfunction]
-fn provider() -> String {
-    "rust".to_string()
-}
-
-/// A Python module implemented in Rust.
-#[pymodule]
-fn openlineage_sql(_py: Python, m: &PyModule) -> PyResult<()> {
-    m.add_function(wrap_pyfunction!(parse, m)?)?;
-    m.add_function(wrap_pyfunction!(provider, m)?)?;
-    m.add_class::<SqlMeta>()?;
-    m.add_class::<DbTableMeta>()?;
-    Ok(())
-}
-
 #[cfg(test)]
 mod tests {
     use crate::DbTableMeta;
@@ -554,14 +1575,50 @@ mod tests {
     fn compare_db_meta() {
         assert_ne!(
             DbTableMeta {
+                server: None,
                 database: None,
                 schema: None,
-                name: "discount".to_string()
+                name: "discount".to_string(),
+                partitions: vec![],
+                wildcard: false,
+                parts: vec![],
+                original_name: "discount".to_string()
             },
             DbTableMeta {
+                server: None,
                 database: None,
                 schema: Some("public".to_string()),
-                name: "discount".to_string()
+                name: "discount".to_string(),
+                partitions: vec![],
+                wildcard: false,
+                parts: vec![],
+                original_name: "public.discount".to_string()
+            }
+        );
+    }
+
+    #[test]
+    fn compare_db_meta_ignores_original_name() {
+        assert_eq!(
+            DbTableMeta {
+                server: None,
+                database: None,
+                schema: None,
+                name: "ORDERS".to_string(),
+                partitions: vec![],
+                wildcard: false,
+                parts: vec![],
+                original_name: "orders".to_string()
+            },
+            DbTableMeta {
+                server: None,
+                database: None,
+                schema: None,
+                name: "ORDERS".to_string(),
+                partitions: vec![],
+                wildcard: false,
+                parts: vec![],
+                original_name: "\"ORDERS\"".to_string()
             }
         );
     }
@@ -0,0 +1,74 @@
+// Copyright 2018-2022 contributors to the OpenLineage project
+// SPDX-License-Identifier: Apache-2.0
+
+// Statements that change lifecycle of tables and which parser doesn't fully know:
+// `TRUNCATE` of several tables or with options, like Postgres' `TRUNCATE a, b CASCADE`,
+// and Snowflake's `UNDROP TABLE`. They are recognized from tokens, like renames are.
+// `DROP` is parsed as usual.
+
+use sqlparser::ast::ObjectName;
+use sqlparser::tokenizer::Token;
+
+use crate::tokens::{is_keyword, split_name, LocatedToken};
+use crate::Operation;
+
+// Tables affected by the statement, with operation applied to each of them, if it's one of
+// statements above. Undropped schema or database isn't a table, so it affects nothing.
+pub(crate) fn parse_lifecycle(tokens: &[LocatedToken]) -> Option<Vec<(ObjectName, Operation)>> {
+    let tokens: Vec<&Token> = tokens
+        .iter()
+        .filter(|t| !t.is_whitespace())
+        .map(|t| &t.token)
+        .collect();
+    match tokens.as_slice() {
+        [truncate, rest @ ..] if is_keyword(truncate, "TRUNCATE") => {
+            let names = parse_truncate(rest)?;
+            Some(
+                names
+                    .into_iter()
+                    .map(|n| (n, Operation::Truncate))
+                    .collect(),
+            )
+        }
+        [undrop, table, name @ ..]
+            if is_keyword(undrop, "UNDROP") && is_keyword(table, "TABLE") =>
+        {
+            let (name, rest) = split_name(name)?;
+            rest.is_empty().then(|| vec![(name, Operation::Undrop)])
+        }
+        [undrop, ..] if is_keyword(undrop, "UNDROP") => Some(vec![]),
+        _ => None,
+    }
+}
+
+// `TRUNCATE [TABLE] [ONLY] [IF EXISTS] a [*] [, b ...] [options]`. Options that follow
+// the names, like `CASCADE`, `RESTART IDENTITY` or Hive's `PARTITION (...)`, don't change
+// which tables are truncated.
+fn parse_truncate(tokens: &[&Token]) -> Option<Vec<ObjectName>> {
+    let mut rest = tokens;
+    for keyword in ["TABLE", "ONLY"] {
+        if let [first, tail @ ..] = rest {
+            if is_keyword(first, keyword) {
+                rest = tail;
+            }
+        }
+    }
+    if let [if_, exists, tail @ ..] = rest {
+        if is_keyword(if_, "IF") && is_keyword(exists, "EXISTS") {
+            rest = tail;
+        }
+    }
+    let mut names = vec![];
+    loop {
+        let (name, tail) = split_name(rest)?;
+        names.push(name);
+        rest = match tail {
+            [Token::Mul, tail @ ..] => tail,
+            _ => tail,
+        };
+        match rest {
+            [Token::Comma, tail @ ..] => rest = tail,
+            _ => return Some(names),
+        }
+    }
+}
@@ -0,0 +1,344 @@
+// Copyright 2018-2022 contributors to the OpenLineage project
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::DbTableMeta;
+
+use sqlparser::ast::{
+    Expr, Function, FunctionArg, FunctionArgExpr, Ident, Query, TableAlias, WindowSpec,
+};
+
+#[cfg_attr(feature = "python", pyo3::pyclass)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Ord, PartialOrd)]
+pub struct ColumnMeta {
+    // Table the column belongs to. None when the parser can't tell which table
+    // the column comes from, for example unqualified column in a join.
+    pub origin: Option<DbTableMeta>,
+    pub name: String,
+}
+
+impl ColumnMeta {
+    pub fn new(name: String, origin: Option<DbTableMeta>) -> Self {
+        ColumnMeta { origin, name }
+    }
+}
+
+// Describes from which input columns single output column was produced.
+#[cfg_attr(feature = "python", pyo3::pyclass)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Ord, PartialOrd)]
+pub struct ColumnLineage {
+    pub descendant: ColumnMeta,
+    pub lineage: Vec<ColumnMeta>,
+}
+
+// Column produced by a query or subquery, together with input columns it was computed from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct OutputColumn {
+    pub name: String,
+    pub sources: Vec<ColumnMeta>,
+}
+
+impl OutputColumn {
+    pub fn new(name: String, mut sources: Vec<ColumnMeta>) -> Self {
+        sources.sort();
+        sources.dedup();
+        OutputColumn { name, sources }
+    }
+
+    pub fn merge(&mut self, other: &OutputColumn) {
+        self.sources.extend(other.sources.iter().cloned());
+        self.sources.sort();
+        self.sources.dedup();
+    }
+}
+
+// Renames columns positionally, for example when CTE or derived table alias specifies
+// column list like in `WITH cte (a, b) AS (...)`, or when INSERT lists target columns.
+pub(crate) fn rename_columns(columns: &mut [OutputColumn], names: &[Ident]) {
+    for (column, name) in columns.iter_mut().zip(names.iter()) {
+        column.name = name.value.clone();
+    }
+}
+
+#[derive(Debug)]
+pub(crate) enum RelationSource {
+    // Table, with its columns if schema provider knows them.
+    Table(DbTableMeta, Option<Vec<String>>),
+    // Subquery or CTE, with columns we were able to discover.
+    Derived(Vec<OutputColumn>),
+    // Reference of recursive CTE to itself. Its columns come from the same sources
+    // as columns of non-recursive part, so they don't add any sources.
+    Recursive,
+}
+
+// Relation visible in FROM clause of a query. Used to resolve what column references point to.
+#[derive(Debug)]
+pub(crate) struct Relation {
+    pub alias: Option<String>,
+    pub source: RelationSource,
+}
+
+impl Relation {
+    pub fn table(
+        table: DbTableMeta,
+        columns: Option<Vec<String>>,
+        alias: Option<&TableAlias>,
+    ) -> Self {
+        Relation {
+            alias: alias.map(|a| a.name.value.clone()),
+            source: RelationSource::Table(table, columns),
+        }
+    }
+
+    pub fn derived(columns: Vec<OutputColumn>, alias: Option<String>) -> Self {
+        Relation {
+            alias,
+            source: RelationSource::Derived(columns),
+        }
+    }
+
+    pub fn recursive(alias: String) -> Self {
+        Relation {
+            alias: Some(alias),
+            source: RelationSource::Recursive,
+        }
+    }
+
+    pub fn matches(&self, qualifier: &[Ident]) -> bool {
+        if let Some(alias) = &self.alias {
+            return qualifier.len() == 1 && qualifier[0].value.eq_ignore_ascii_case(alias);
+        }
+        match &self.source {
+            RelationSource::Table(table, _) => {
+                let parts = [&table.database, &table.schema];
+                let mut qualifier = qualifier.iter().rev();
+                match qualifier.next() {
+                    Some(name) if name.value.eq_ignore_ascii_case(&table.name) => {}
+                    _ => return false,
+                }
+                qualifier
+                    .zip(parts.iter().rev())
+                    .all(|(ident, part)| match part {
+                        Some(part) => ident.value.eq_ignore_ascii_case(part),
+                        None => false,
+                    })
+            }
+            RelationSource::Derived(_) | RelationSource::Recursive => false,
+        }
+    }
+
+    // Table this relation reads from, if it's a table aliased as `alias`.
+    pub fn aliased_table(&self, alias: &str) -> Option<DbTableMeta> {
+        match (&self.alias, &self.source) {
+            (Some(a), RelationSource::Table(table, _)) if a.eq_ignore_ascii_case(alias) => {
+                Some(table.clone())
+            }
+            _ => None,
+        }
+    }
+
+    // Looks for column in relation. Any column is assumed to exist in table with unknown schema.
+    fn find_column(&self, name: &str) -> Option<Vec<ColumnMeta>> {
+        match &self.source {
+            RelationSource::Table(_, None) => self.table_column(name),
+            RelationSource::Recursive => Some(vec![]),
+            _ => self.find_known_column(name),
+        }
+    }
+
+    // Looks for column only among columns we know relation has.
+    fn find_known_column(&self, name: &str) -> Option<Vec<ColumnMeta>> {
+        match &self.source {
+            RelationSource::Table(_, Some(columns)) => columns
+                .iter()
+                .find(|c| c.eq_ignore_ascii_case(name))
+                .and_then(|c| self.table_column(c)),
+            RelationSource::Table(_, None) | RelationSource::Recursive => None,
+            RelationSource::Derived(columns) => columns
+                .iter()
+                .find(|c| c.name.eq_ignore_ascii_case(name))
+                .map(|c| c.sources.clone()),
+        }
+    }
+
+    fn table_column(&self, name: &str) -> Option<Vec<ColumnMeta>> {
+        match &self.source {
+            RelationSource::Table(table, _) => {
+                Some(vec![ColumnMeta::new(name.to_string(), Some(table.clone()))])
+            }
+            RelationSource::Derived(_) | RelationSource::Recursive => None,
+        }
+    }
+
+    // All columns of the relation, used to expand wildcards. None if we don't know them.
+    pub fn columns(&self) -> Option<Vec<OutputColumn>> {
+        match &self.source {
+            RelationSource::Table(table, Some(columns)) => Some(
+                columns
+                    .iter()
+                    .map(|c| {
+                        OutputColumn::new(
+                            c.clone(),
+                            vec![ColumnMeta::new(c.clone(), Some(table.clone()))],
+                        )
+                    })
+                    .collect(),
+            ),
+            RelationSource::Table(_, None) | RelationSource::Recursive => None,
+            RelationSource::Derived(columns) => Some(columns.clone()),
+        }
+    }
+}
+
+// Resolves column reference, possibly qualified with table name or alias, to the input columns
+// it points to. Scopes are searched starting from the innermost one, so that lateral and
+// correlated subqueries can refer to relations of queries that contain them. If we can't tell
+// which relation column belongs to, origin of the column is unknown.
+pub(crate) fn resolve_column(scopes: &[&[Relation]], ident: &[Ident]) -> Vec<ColumnMeta> {
+    let (name, qualifier) = match ident.split_last() {
+        Some(x) => x,
+        None => return vec![],
+    };
+    let find = |scope: &[Relation]| {
+        if qualifier.is_empty() {
+            scope
+                .iter()
+                .find_map(|r| r.find_known_column(&name.value))
+                .or_else(|| match scope {
+                    [relation] => relation.find_column(&name.value),
+                    _ => None,
+                })
+        } else {
+            scope
+                .iter()
+                .find(|r| r.matches(qualifier))
+                .and_then(|r| r.find_column(&name.value))
+        }
+    };
+    scopes
+        .iter()
+        .find_map(|scope| find(scope))
+        .unwrap_or_else(|| vec![ColumnMeta::new(name.value.clone(), None)])
+}
+
+// Name of the column produced by unaliased expression in SELECT list.
+pub(crate) fn expr_name(expr: &Expr) -> String {
+    match expr {
+        Expr::Identifier(ident) => ident.value.clone(),
+        Expr::CompoundIdentifier(idents) => idents
+            .last()
+            .map(|i| i.value.clone())
+            .unwrap_or_else(|| expr.to_string()),
+        _ => expr.to_string(),
+    }
+}
+
+// Column references and subqueries found in a single expression.
+#[derive(Debug, Default)]
+pub(crate) struct ExprRefs<'a> {
+    pub columns: Vec<&'a [Ident]>,
+    pub queries: Vec<&'a Query>,
+}
+
+impl<'a> ExprRefs<'a> {
+    pub fn collect(expr: &'a Expr) -> Self {
+        let mut refs = ExprRefs::default();
+        refs.visit_expr(expr);
+        refs
+    }
+
+    fn visit_exprs(&mut self, exprs: &'a [Expr]) {
+        for expr in exprs {
+            self.visit_expr(expr);
+        }
+    }
+
+    fn visit_function(&mut self, function: &'a Function) {
+        for arg in &function.args {
+            let arg = match arg {
+                FunctionArg::Named { arg, .. } => arg,
+                FunctionArg::Unnamed(arg) => arg,
+            };
+            if let FunctionArgExpr::Expr(expr) = arg {
+                self.visit_expr(expr);
+            }
+        }
+        if let Some(WindowSpec {
+            partition_by,
+            order_by,
+            ..
+        }) = &function.over
+        {
+            self.visit_exprs(partition_by);
+            for order in order_by {
+                self.visit_expr(&order.expr);
+            }
+        }
+    }
+
+    fn visit_expr(&mut self, expr: &'a Expr) {
+        match expr {
+            Expr::Identifier(ident) => self.columns.push(std::slice::from_ref(ident)),
+            Expr::CompoundIdentifier(idents) => self.columns.push(idents.as_slice()),
+            Expr::Subquery(query) => self.queries.push(query),
+            Expr::InSubquery { expr, subquery, .. } => {
+                self.visit_expr(expr);
+                self.queries.push(subquery);
+            }
+            Expr::Exists { subquery, .. } => self.queries.push(subquery),
+            Expr::BinaryOp { left, right, .. } => {
+                self.visit_expr(left);
+                self.visit_expr(right);
+            }
+            Expr::UnaryOp { expr, .. }
+            | Expr::Nested(expr)
+            | Expr::Cast { expr, .. }
+            | Expr::TryCast { expr, .. }
+            | Expr::Extract { expr, .. }
+            | Expr::IsNull(expr)
+            | Expr::IsNotNull(expr)
+            | Expr::MapAccess { column: expr, .. } => self.visit_expr(expr),
+            Expr::InList { expr, list, .. } => {
+                self.visit_expr(expr);
+                self.visit_exprs(list);
+            }
+            Expr::Between {
+                expr, low, high, ..
+            } => {
+                self.visit_expr(expr);
+                self.visit_expr(low);
+                self.visit_expr(high);
+            }
+            Expr::Case {
+                operand,
+                conditions,
+                results,
+                else_result,
+            } => {
+                if let Some(operand) = operand {
+                    self.visit_expr(operand);
+                }
+                self.visit_exprs(conditions);
+                self.visit_exprs(results);
+                if let Some(else_result) = else_result {
+                    self.visit_expr(else_result);
+                }
+            }
+            Expr::Function(function) => self.visit_function(function),
+            Expr::Substring {
+                expr,
+                substring_from,
+                substring_for,
+            } => {
+                self.visit_expr(expr);
+                if let Some(from) = substring_from {
+                    self.visit_expr(from);
+                }
+                if let Some(len) = substring_for {
+                    self.visit_expr(len);
+                }
+            }
+            Expr::Tuple(exprs) => self.visit_exprs(exprs),
+            _ => {}
+        }
+    }
+}
@@ -0,0 +1,337 @@
+// Copyright 2018-2022 contributors to the OpenLineage project
+// SPDX-License-Identifier: Apache-2.0
+
+// The same dataset is often known under different names: environments prefix their schemas,
+// like `dev_mart` and `mart`, databases have aliases, and views just rename tables. Mapping
+// rewrites names of extracted tables, so that lineage of all of them meets on one dataset.
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+use regex::Regex;
+use serde::Deserialize;
+
+use crate::{DbTableMeta, NamePart};
+
+/// Part of qualified table name that prefix and suffix rules rewrite.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum NameField {
+    Database,
+    Schema,
+    Table,
+}
+
+#[derive(Debug, Clone)]
+enum Rule {
+    Prefix {
+        field: NameField,
+        from: String,
+        to: String,
+    },
+    Suffix {
+        field: NameField,
+        from: String,
+        to: String,
+    },
+    Regex {
+        pattern: Regex,
+        replacement: String,
+    },
+}
+
+/// Rewrites names of tables after they are extracted. Prefix, suffix and regex rules are
+/// applied in order they were added, then the result is replaced by its synonym, if it has one.
+/// Names are compared case insensitively.
+#[derive(Debug, Clone, Default)]
+pub struct NameMapping {
+    rules: Vec<Rule>,
+    // Lowercase qualified names, mapped to names they are synonyms of.
+    synonyms: HashMap<String, String>,
+}
+
+impl NameMapping {
+    pub fn new() -> Self {
+        NameMapping::default()
+    }
+
+    /// Replaces prefix `from` of the part of the name with `to`, like `dev_` with nothing.
+    pub fn add_prefix(&mut self, field: NameField, from: &str, to: &str) {
+        self.rules.push(Rule::Prefix {
+            field,
+            from: String::from(from),
+            to: String::from(to),
+        });
+    }
+
+    /// Replaces suffix `from` of the part of the name with `to`.
+    pub fn add_suffix(&mut self, field: NameField, from: &str, to: &str) {
+        self.rules.push(Rule::Suffix {
+            field,
+            from: String::from(from),
+            to: String::from(to),
+        });
+    }
+
+    /// Replaces all matches of regex in qualified name, like `db.schema.table`, with
+    /// replacement, which can refer to capture groups as `$1` or `${name}`. Parts that contain
+    /// dots are double-quoted in the name, like `"my.schema".table`.
+    pub fn add_regex(&mut self, pattern: &str, replacement: &str) -> Result<(), String> {
+        let pattern = Regex::new(pattern)
+            .map_err(|e| format!("invalid name mapping pattern {}: {}", pattern, e))?;
+        self.rules.push(Rule::Regex {
+            pattern,
+            replacement: String::from(replacement),
+        });
+        Ok(())
+    }
+
+    /// Makes qualified name `name` refer to table `target`. Parts of both that contain dots
+    /// are double-quoted, like `"my.schema".table`.
+    pub fn add_synonym(&mut self, name: &str, target: &str) {
+        self.synonyms
+            .insert(name.to_lowercase(), String::from(target));
+    }
+
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Self, String> {
+        let content = fs::read_to_string(path.as_ref()).map_err(|e| {
+            format!(
+                "can't read name mapping file {}: {}",
+                path.as_ref().display(),
+                e
+            )
+        })?;
+        NameMapping::from_json(&content)
+    }
+
+    /// Reads mapping from JSON like:
+    /// `{"prefixes": [{"field": "schema", "from": "dev_", "to": ""}],
+    ///   "suffixes": [{"field": "database", "from": "_alias", "to": ""}],
+    ///   "regex": [{"pattern": "^staging\\.", "replacement": "mart."}],
+    ///   "synonyms": {"mart.orders_v": "mart.orders"}}`
+    /// All keys are optional. Rules are added in order: prefixes, suffixes, then regexes.
+    pub fn from_json(content: &str) -> Result<Self, String> {
+        let config: MappingConfig = serde_json::from_str(content)
+            .map_err(|e| format!("can't parse name mapping: {}", e))?;
+        let mut mapping = NameMapping::new();
+        for rule in config.prefixes {
+            mapping.add_prefix(rule.field, &rule.from, &rule.to);
+        }
+        for rule in config.suffixes {
+            mapping.add_suffix(rule.field, &rule.from, &rule.to);
+        }
+        for rule in config.regex {
+            mapping.add_regex(&rule.pattern, &rule.replacement)?;
+        }
+        for (name, target) in config.synonyms {
+            mapping.add_synonym(&name, &target);
+        }
+        Ok(mapping)
+    }
+
+    /// Table name rewritten by the mapping. Parts and original spelling of rewritten name
+    /// are those of the new name, with quoting of the old parts kept where they remain.
+    pub fn map(&self, original: &DbTableMeta) -> DbTableMeta {
+        let mut table = original.clone();
+        for rule in &self.rules {
+            table = match rule {
+                Rule::Prefix { field, from, to } => map_field(table, *field, |part| {
+                    strip_prefix(part, from).map(|rest| format!("{}{}", to, rest))
+                }),
+                Rule::Suffix { field, from, to } => map_field(table, *field, |part| {
+                    strip_suffix(part, from).map(|rest| format!("{}{}", rest, to))
+                }),
+                Rule::Regex {
+                    pattern,
+                    replacement,
+                } => {
+                    let name = mapping_name(&table);
+                    match pattern.replace_all(&name, replacement.as_str()) {
+                        mapped if mapped != name => with_qualified_name(table, &mapped),
+                        _ => table,
+                    }
+                }
+            };
+        }
+        let table = match self.synonyms.get(&mapping_name(&table).to_lowercase()) {
+            Some(target) => with_qualified_name(table, target),
+            None => table,
+        };
+        if table == *original {
+            original.clone()
+        } else {
+            respell(table, original)
+        }
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct MappingConfig {
+    #[serde(default)]
+    prefixes: Vec<AffixConfig>,
+    #[serde(default)]
+    suffixes: Vec<AffixConfig>,
+    #[serde(default)]
+    regex: Vec<RegexConfig>,
+    #[serde(default)]
+    synonyms: HashMap<String, String>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct AffixConfig {
+    field: NameField,
+    from: String,
+    #[serde(default)]
+    to: String,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RegexConfig {
+    pattern: String,
+    replacement: String,
+}
+
+fn map_field<F>(table: DbTableMeta, field: NameField, f: F) -> DbTableMeta
+where
+    F: Fn(&str) -> Option<String>,
+{
+    match field {
+        NameField::Database => DbTableMeta {
+            database: table.database.as_deref().and_then(&f).or(table.database),
+            ..table
+        },
+        NameField::Schema => DbTableMeta {
+            schema: table.schema.as_deref().and_then(&f).or(table.schema),
+            ..table
+        },
+        NameField::Table => DbTableMeta {
+            name: f(&table.name).unwrap_or(table.name),
+            ..table
+        },
+    }
+}
+
+fn strip_prefix<'a>(part: &'a str, prefix: &str) -> Option<&'a str> {
+    let head = part.get(..prefix.len())?;
+    if head.eq_ignore_ascii_case(prefix) {
+        Some(&part[prefix.len()..])
+    } else {
+        None
+    }
+}
+
+fn strip_suffix<'a>(part: &'a str, suffix: &str) -> Option<&'a str> {
+    let start = part.len().checked_sub(suffix.len())?;
+    let tail = part.get(start..)?;
+    if tail.eq_ignore_ascii_case(suffix) {
+        Some(&part[..start])
+    } else {
+        None
+    }
+}
+
+fn fields(table: &DbTableMeta) -> Vec<&str> {
+    [&table.server, &table.database, &table.schema]
+        .into_iter()
+        .flatten()
+        .chain(std::iter::once(&table.name))
+        .map(String::as_str)
+        .collect()
+}
+
+fn needs_quotes(part: &str) -> bool {
+    part.contains('.') || part.contains('"')
+}
+
+// Qualified name that rules see, with parts that contain dots or quotes double-quoted, like
+// `"my.schema".orders`, so that it can be split back into the same parts.
+fn mapping_name(table: &DbTableMeta) -> String {
+    fields(table)
+        .into_iter()
+        .map(|part| {
+            if needs_quotes(part) {
+                format!("\"{}\"", part.replace('"', "\"\""))
+            } else {
+                String::from(part)
+            }
+        })
+        .collect::<Vec<String>>()
+        .join(".")
+}
+
+// Splits qualified name written like `mapping_name` makes it into its parts.
+fn split_name(name: &str) -> Vec<String> {
+    let mut parts = vec![];
+    let mut part = String::new();
+    let mut quoted = false;
+    let mut chars = name.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if quoted && chars.peek() == Some(&'"') => {
+                chars.next();
+                part.push('"');
+            }
+            '"' => quoted = !quoted,
+            '.' if !quoted => parts.push(std::mem::take(&mut part)),
+            _ => part.push(c),
+        }
+    }
+    parts.push(part);
+    parts
+}
+
+// Table renamed to qualified name, like `db.schema.table`. Parts beyond four are kept together
+// as the server.
+fn with_qualified_name(table: DbTableMeta, name: &str) -> DbTableMeta {
+    let mut parts = split_name(name);
+    let name = parts.pop().unwrap_or_default();
+    let schema = parts.pop();
+    let database = parts.pop();
+    let server = if parts.is_empty() {
+        None
+    } else {
+        Some(parts.join("."))
+    };
+    DbTableMeta {
+        server,
+        database,
+        schema,
+        name,
+        ..table
+    }
+}
+
+// Parts and original name of the table rebuilt from its mapped name. Parts keep quoting of
+// original ones at the same position, counting from the end, and are quoted if they have to be.
+fn respell(table: DbTableMeta, original: &DbTableMeta) -> DbTableMeta {
+    let values = fields(&table);
+    let offset = original.parts.len() as isize - values.len() as isize;
+    let parts: Vec<NamePart> = values
+        .iter()
+        .enumerate()
+        .map(|(i, value)| {
+            let quote_style = usize::try_from(i as isize + offset)
+                .ok()
+                .and_then(|j| original.parts.get(j))
+                .and_then(|part| part.quote_style);
+            NamePart {
+                value: String::from(*value),
+                quote_style: quote_style.or_else(|| needs_quotes(value).then_some('"')),
+            }
+        })
+        .collect();
+    let original_name = parts
+        .iter()
+        .map(NamePart::raw)
+        .collect::<Vec<String>>()
+        .join(".");
+    DbTableMeta {
+        parts,
+        original_name,
+        ..table
+    }
+}
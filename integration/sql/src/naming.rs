@@ -0,0 +1,208 @@
+// Copyright 2018-2022 contributors to the OpenLineage project
+// SPDX-License-Identifier: Apache-2.0
+
+// Turns tables into OpenLineage dataset identifiers, as defined in spec/Naming.md.
+// Namespace identifies the data source, and name identifies the table within it.
+
+use crate::DbTableMeta;
+
+/// Data source that tables belong to. Only some fields are used by each scheme:
+/// Postgres, MySQL, Hive and SQL Server need `host`, Redshift needs `host` and optionally
+/// `region`, Snowflake needs `account`. When `port` is not set, default port of the database
+/// is used.
+#[cfg_attr(feature = "python", pyo3::pyclass)]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Connection {
+    /// Kind of data source, like `postgres`, `snowflake` or `bigquery`.
+    pub scheme: String,
+    pub host: Option<String>,
+    pub port: Option<u16>,
+    /// Snowflake account. If not set, it's taken from `host`.
+    pub account: Option<String>,
+    /// BigQuery project of tables that don't specify it.
+    pub project: Option<String>,
+    /// Region of Redshift cluster. If not set, it's taken from `host`.
+    pub region: Option<String>,
+    /// Database of tables that don't specify it.
+    pub database: Option<String>,
+}
+
+/// OpenLineage identifier of a dataset.
+#[cfg_attr(feature = "python", pyo3::pyclass)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct DatasetName {
+    pub namespace: String,
+    pub name: String,
+}
+
+impl Connection {
+    pub fn new(scheme: &str) -> Self {
+        Connection {
+            scheme: String::from(scheme),
+            ..Connection::default()
+        }
+    }
+
+    /// Namespace of tables of the data source. SQL Server namespace includes database,
+    /// so for it tables of other databases have different namespace than this.
+    pub fn namespace(&self) -> Result<String, String> {
+        self.table_namespace(self.database.as_deref())
+    }
+
+    /// Namespace and name of the table. Fails if table name or connection lack parts
+    /// that identifier of the scheme requires.
+    pub fn dataset_name(&self, table: &DbTableMeta) -> Result<DatasetName, String> {
+        if let Some(server) = &table.server {
+            return self.linked_server_dataset_name(server, table);
+        }
+        let database = || self.part(&table.database, &self.database, "database", table);
+        let schema = || self.part(&table.schema, &None, "schema", table);
+        let name = match self.scheme.as_str() {
+            "postgres" | "postgresql" | "redshift" | "snowflake" => {
+                format!("{}.{}.{}", database()?, schema()?, table.name)
+            }
+            "bigquery" => format!(
+                "{}.{}.{}",
+                self.part(&table.database, &self.project, "project", table)?,
+                self.part(&table.schema, &None, "dataset", table)?,
+                table.name
+            ),
+            // These databases have no schemas: what they call database is the part
+            // of the name that other dialects call schema.
+            "mysql" | "hive" => {
+                if table.database.is_some() {
+                    return Err(format!(
+                        "table {} has too many parts for {}",
+                        table.qualified_name(),
+                        self.scheme
+                    ));
+                }
+                format!(
+                    "{}.{}",
+                    self.part(&table.schema, &self.database, "database", table)?,
+                    table.name
+                )
+            }
+            "sqlserver" | "mssql" => {
+                return Ok(DatasetName {
+                    namespace: self.table_namespace(Some(&database()?))?,
+                    name: format!("{}.{}", schema()?, table.name),
+                })
+            }
+            _ => return Err(format!("unsupported scheme {}", self.scheme)),
+        };
+        Ok(DatasetName {
+            namespace: self.namespace()?,
+            name,
+        })
+    }
+
+    // Table on SQL Server linked server, or remote server of OPENROWSET. Server is assumed
+    // to be named after its host, like `host` or `host,port`.
+    fn linked_server_dataset_name(
+        &self,
+        server: &str,
+        table: &DbTableMeta,
+    ) -> Result<DatasetName, String> {
+        if self.scheme != "sqlserver" && self.scheme != "mssql" {
+            return Err(format!(
+                "{} name can't refer to server, like table {} does",
+                self.scheme,
+                table.qualified_name()
+            ));
+        }
+        let (host, port) = match server.split_once(',') {
+            Some((host, port)) => (host, port.trim().parse().ok()),
+            None => (server, None),
+        };
+        let remote = Connection {
+            host: Some(String::from(host)),
+            port,
+            database: None,
+            ..self.clone()
+        };
+        remote.dataset_name(&DbTableMeta {
+            server: None,
+            ..table.clone()
+        })
+    }
+
+    fn table_namespace(&self, database: Option<&str>) -> Result<String, String> {
+        match self.scheme.as_str() {
+            "postgres" | "postgresql" => Ok(format!("postgres://{}", self.authority(5432)?)),
+            "mysql" => Ok(format!("mysql://{}", self.authority(3306)?)),
+            "hive" => Ok(format!("hive://{}", self.authority(10000)?)),
+            "redshift" => Ok(format!("redshift://{}", self.redshift_authority()?)),
+            "snowflake" => Ok(format!("snowflake://{}", self.snowflake_account()?)),
+            "bigquery" => Ok(String::from("bigquery")),
+            "sqlserver" | "mssql" => match database {
+                Some(database) => Ok(format!(
+                    "sqlserver://{};database={};",
+                    self.authority(1433)?,
+                    database
+                )),
+                None => Err(String::from("sqlserver namespace requires database")),
+            },
+            _ => Err(format!("unsupported scheme {}", self.scheme)),
+        }
+    }
+
+    fn host(&self) -> Result<&str, String> {
+        self.host
+            .as_deref()
+            .ok_or_else(|| format!("{} namespace requires host", self.scheme))
+    }
+
+    fn authority(&self, default_port: u16) -> Result<String, String> {
+        Ok(format!(
+            "{}:{}",
+            self.host()?,
+            self.port.unwrap_or(default_port)
+        ))
+    }
+
+    // Cluster identifier and region, taken from endpoint like
+    // `examplecluster.abc123xyz789.us-west-2.redshift.amazonaws.com` if not given.
+    fn redshift_authority(&self) -> Result<String, String> {
+        let host = self.host()?;
+        let labels: Vec<&str> = host.split('.').collect();
+        let cluster = labels[0];
+        let region = match (&self.region, labels.as_slice()) {
+            (Some(region), _) => region.as_str(),
+            (None, [_, _, region, "redshift", ..]) => region,
+            (None, _) => return Err(format!("can't tell region of redshift cluster {}", host)),
+        };
+        Ok(format!(
+            "{}.{}:{}",
+            cluster,
+            region,
+            self.port.unwrap_or(5439)
+        ))
+    }
+
+    // Account, taken from URL like `xy12345.us-east-1.snowflakecomputing.com` if not given.
+    fn snowflake_account(&self) -> Result<String, String> {
+        match (&self.account, &self.host) {
+            (Some(account), _) => Ok(account.clone()),
+            (None, Some(host)) => Ok(host.trim_end_matches(".snowflakecomputing.com").to_string()),
+            (None, None) => Err(String::from("snowflake namespace requires account")),
+        }
+    }
+
+    fn part(
+        &self,
+        part: &Option<String>,
+        default: &Option<String>,
+        kind: &str,
+        table: &DbTableMeta,
+    ) -> Result<String, String> {
+        part.clone().or_else(|| default.clone()).ok_or_else(|| {
+            format!(
+                "{} name requires {}, which table {} doesn't specify",
+                self.scheme,
+                kind,
+                table.qualified_name()
+            )
+        })
+    }
+}
@@ -0,0 +1,200 @@
+// Copyright 2018-2022 contributors to the OpenLineage project
+// SPDX-License-Identifier: Apache-2.0
+
+// Where in SQL text input and output tables are referenced. Parser doesn't keep positions
+// of AST nodes, so names of tables the statement references are looked for in its tokens.
+
+use sqlparser::ast::Ident;
+use sqlparser::tokenizer::Token;
+
+use crate::tokens::{is_keyword, LocatedToken, Location};
+use crate::DbTableMeta;
+
+/// Whether table is read or written where it's referenced.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum TableRole {
+    Input,
+    Output,
+}
+
+impl TableRole {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TableRole::Input => "input",
+            TableRole::Output => "output",
+        }
+    }
+}
+
+/// Reference to input or output table in SQL text. Locations are in the SQL text
+/// the statement is part of, so `start.offset..end.offset` is byte range of the name there.
+#[cfg_attr(feature = "python", pyo3::pyclass)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct TableOccurrence {
+    pub statement_index: usize,
+    pub start: Location,
+    pub end: Location,
+    pub table: DbTableMeta,
+    pub role: TableRole,
+}
+
+// Table the statement references, with its name as written.
+#[derive(Debug, Clone)]
+pub(crate) struct TableReference {
+    pub name: Vec<Ident>,
+    pub table: DbTableMeta,
+    pub role: TableRole,
+}
+
+// Occurrences of referenced tables in tokens of the statement, in order they appear.
+pub(crate) fn locate(
+    statement_index: usize,
+    references: &[TableReference],
+    tokens: &[LocatedToken],
+) -> Vec<TableOccurrence> {
+    let tokens: Vec<&LocatedToken> = tokens.iter().filter(|t| !t.is_whitespace()).collect();
+    let mut occurrences: Vec<TableOccurrence> = vec![];
+    for reference in references {
+        // Name the statement both reads and writes, like in `INSERT INTO t SELECT * FROM t`,
+        // is told apart by keyword that precedes it.
+        let ambiguous = references
+            .iter()
+            .any(|other| other.name == reference.name && other.role != reference.role);
+        for (first, last) in find_name(&tokens, &reference.name) {
+            if ambiguous && role_at(&tokens, first) != reference.role {
+                continue;
+            }
+            occurrences.push(TableOccurrence {
+                statement_index,
+                start: tokens[first].start,
+                end: tokens[last].end,
+                table: reference.table.clone(),
+                role: reference.role,
+            });
+        }
+    }
+    occurrences.sort();
+    occurrences.dedup();
+    occurrences
+}
+
+// Indexes of the first and the last token of each place where dot-separated name is written
+// as a table. Name that is a part of longer one, like `t` in `t.column`, doesn't count, and
+// neither does column or alias spelled like the table.
+fn find_name(tokens: &[&LocatedToken], name: &[Ident]) -> Vec<(usize, usize)> {
+    if name.is_empty() {
+        return vec![];
+    }
+    let len = name.len() * 2 - 1;
+    let is_period = |i: usize| matches!(tokens.get(i), Some(t) if t.token == Token::Period);
+    let follows_period = |i: usize| i > 0 && is_period(i - 1);
+    (0..tokens.len())
+        .filter(|start| start + len <= tokens.len())
+        .filter(|start| {
+            name.iter().enumerate().all(|(i, ident)| {
+                let word = match &tokens[start + i * 2].token {
+                    Token::Word(word) => word,
+                    _ => return false,
+                };
+                word.value == ident.value
+                    && word.quote_style == ident.quote_style
+                    && (i == 0 || is_period(start + i * 2 - 1))
+            })
+        })
+        .filter(|start| !follows_period(*start) && !is_period(start + len))
+        .filter(|start| is_table_position(tokens, *start))
+        .map(|start| (start, start + len - 1))
+        .collect()
+}
+
+// Keywords that table names follow, like `FROM` or `INTO`, including modifiers that can come
+// between them and the name, like `ONLY` or `IF EXISTS`.
+const TABLE_KEYWORDS: &[&str] = &[
+    "FROM",
+    "JOIN",
+    "INTO",
+    "UPDATE",
+    "TABLE",
+    "VIEW",
+    "USING",
+    "MERGE",
+    "ONLY",
+    "EXISTS",
+    "OVERWRITE",
+    "COPY",
+    "CLONE",
+    "LIKE",
+    "TO",
+    "TRUNCATE",
+];
+
+// Keywords that start parts of statement other than lists of tables.
+const CLAUSE_KEYWORDS: &[&str] = &[
+    "SELECT",
+    "WHERE",
+    "ON",
+    "SET",
+    "BY",
+    "HAVING",
+    "QUALIFY",
+    "VALUES",
+    "AND",
+    "OR",
+    "WHEN",
+    "THEN",
+    "ELSE",
+    "RETURNING",
+    "LIMIT",
+];
+
+// Whether name starting at index is written where table is expected: right after keyword
+// that table name follows, or after comma in a list of such names, like `FROM a, b`.
+fn is_table_position(tokens: &[&LocatedToken], index: usize) -> bool {
+    let is_any = |token: &Token, keywords: &[&str]| keywords.iter().any(|k| is_keyword(token, k));
+    let previous = match index {
+        0 => return false,
+        _ => &tokens[index - 1].token,
+    };
+    if is_any(previous, TABLE_KEYWORDS) {
+        return true;
+    }
+    // Snowflake's `ALTER TABLE a SWAP WITH b`. Otherwise, WITH is followed by names of CTEs.
+    if is_keyword(previous, "WITH") {
+        return index >= 2 && is_keyword(&tokens[index - 2].token, "SWAP");
+    }
+    if *previous != Token::Comma {
+        return false;
+    }
+    // Keyword the list starts with decides. Parenthesized parts, like column aliases of
+    // a table, are skipped.
+    let mut depth = 0;
+    for token in tokens[..index - 1].iter().rev().map(|t| &t.token) {
+        match token {
+            Token::RParen => depth += 1,
+            Token::LParen if depth == 0 => return false,
+            Token::LParen => depth -= 1,
+            _ if depth > 0 => {}
+            Token::Word(_) if is_any(token, TABLE_KEYWORDS) => return true,
+            Token::Word(_) if is_any(token, CLAUSE_KEYWORDS) => return false,
+            Token::Word(_) | Token::Period | Token::Comma => {}
+            _ => return false,
+        }
+    }
+    false
+}
+
+fn role_at(tokens: &[&LocatedToken], index: usize) -> TableRole {
+    let keyword = |i: usize| match tokens.get(i).map(|t| &t.token) {
+        Some(Token::Word(word)) if word.quote_style.is_none() => word.value.to_uppercase(),
+        _ => String::new(),
+    };
+    let previous = match index {
+        0 => String::new(),
+        _ => keyword(index - 1),
+    };
+    match previous.as_str() {
+        "INTO" | "UPDATE" | "TABLE" | "VIEW" => TableRole::Output,
+        "FROM" if index >= 2 && keyword(index - 2) == "DELETE" => TableRole::Output,
+        _ => TableRole::Input,
+    }
+}
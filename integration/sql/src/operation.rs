@@ -0,0 +1,100 @@
+// Copyright 2018-2022 contributors to the OpenLineage project
+// SPDX-License-Identifier: Apache-2.0
+
+use std::fmt;
+
+use crate::DbTableMeta;
+
+/// What statement does to its output table.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Operation {
+    /// Rows are added, like in `INSERT INTO`.
+    Append,
+    /// Table content is replaced, like in `INSERT OVERWRITE`.
+    Overwrite,
+    /// Rows are inserted or updated, like in `MERGE` or `INSERT ... ON DUPLICATE KEY UPDATE`.
+    Upsert,
+    Update,
+    Delete,
+    /// Table is created, like in `CREATE TABLE` or `SELECT ... INTO`.
+    Create,
+    CreateOrReplace,
+    /// All rows are removed, like in `TRUNCATE`.
+    Truncate,
+    Drop,
+    Rename,
+    /// Dropped table is restored, like in Snowflake's `UNDROP TABLE`.
+    Undrop,
+}
+
+impl Operation {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Operation::Append => "append",
+            Operation::Overwrite => "overwrite",
+            Operation::Upsert => "upsert",
+            Operation::Update => "update",
+            Operation::Delete => "delete",
+            Operation::Create => "create",
+            Operation::CreateOrReplace => "create_or_replace",
+            Operation::Truncate => "truncate",
+            Operation::Drop => "drop",
+            Operation::Rename => "rename",
+            Operation::Undrop => "undrop",
+        }
+    }
+
+    /// State of `lifecycleStateChange` in LifecycleStateChangeDatasetFacet, for operations
+    /// that change the table as a whole rather than its rows. Restored table is created
+    /// again, as far as the spec is concerned.
+    pub fn lifecycle_state_change(&self) -> Option<&'static str> {
+        match self {
+            Operation::Create | Operation::CreateOrReplace | Operation::Undrop => Some("CREATE"),
+            Operation::Overwrite => Some("OVERWRITE"),
+            Operation::Truncate => Some("TRUNCATE"),
+            Operation::Drop => Some("DROP"),
+            Operation::Rename => Some("RENAME"),
+            Operation::Append | Operation::Upsert | Operation::Update | Operation::Delete => None,
+        }
+    }
+}
+
+impl fmt::Display for Operation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+/// Output table together with operation applied to it. Table written by multiple
+/// statements of a script has an entry for each distinct operation.
+#[cfg_attr(feature = "python", pyo3::pyclass)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct DatasetOperation {
+    pub table: DbTableMeta,
+    pub operation: Operation,
+}
+
+impl DatasetOperation {
+    pub fn new(table: DbTableMeta, operation: Operation) -> Self {
+        DatasetOperation { table, operation }
+    }
+}
+
+/// Change of table's lifecycle state, with index of the statement that made it, so that
+/// the state a script leaves the table in can be told.
+#[cfg_attr(feature = "python", pyo3::pyclass)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct LifecycleChange {
+    pub statement_index: usize,
+    pub table: DbTableMeta,
+    pub operation: Operation,
+}
+
+/// Table renamed by a statement. Snowflake's `ALTER TABLE a SWAP WITH b` renames two tables:
+/// `a` to `b` and `b` to `a`.
+#[cfg_attr(feature = "python", pyo3::pyclass)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct TableRename {
+    pub from: DbTableMeta,
+    pub to: DbTableMeta,
+}
@@ -0,0 +1,708 @@
+// Copyright 2018-2022 contributors to the OpenLineage project
+// SPDX-License-Identifier: Apache-2.0
+
+// Python bindings. Everything pyo3-specific lives here, so that the crate can be used
+// from pure Rust without linking libpython.
+
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+use std::sync::Arc;
+
+use crate::{
+    get_generic_dialect, parse_multiple_statements_with_options, parse_statements_with_options,
+    ColumnLineage, ColumnMeta, Connection, DatasetName, DatasetOperation, DbTableMeta,
+    ExtractionError, InMemorySchemaProvider, LifecycleChange, Location, NameMapping, NamePart,
+    ParseError, ParseOptions, SchemaProvider, SqlMeta, StatementMeta, TableOccurrence,
+    TablePattern, TableRename, PRODUCER,
+};
+use pyo3::basic::CompareOp;
+use pyo3::create_exception;
+use pyo3::exceptions::{PyRuntimeError, PyTypeError, PyValueError};
+use pyo3::prelude::*;
+
+// Base of all errors raised by `parse`. Subclasses RuntimeError, which was raised before.
+create_exception!(openlineage_sql, SqlParseError, PyRuntimeError);
+create_exception!(openlineage_sql, SqlSyntaxError, SqlParseError);
+create_exception!(openlineage_sql, UnsupportedSqlError, SqlParseError);
+create_exception!(openlineage_sql, EmptySqlError, SqlParseError);
+
+impl From<ParseError> for PyErr {
+    fn from(err: ParseError) -> PyErr {
+        match err {
+            ParseError::Syntax { .. } => SqlSyntaxError::new_err(err.to_string()),
+            ParseError::Unsupported { .. } => UnsupportedSqlError::new_err(err.to_string()),
+            ParseError::EmptyInput => EmptySqlError::new_err(err.to_string()),
+        }
+    }
+}
+
+#[pymethods]
+impl DbTableMeta {
+    #[new]
+    pub fn py_new(name: String) -> Self {
+        DbTableMeta::new_default_dialect(name)
+    }
+
+    #[getter(server)]
+    fn py_server(&self) -> Option<String> {
+        self.server.clone()
+    }
+
+    #[getter(database)]
+    fn py_database(&self) -> Option<String> {
+        self.database.clone()
+    }
+
+    #[getter(schema)]
+    fn py_schema(&self) -> Option<String> {
+        self.schema.clone()
+    }
+
+    #[getter(name)]
+    fn py_name(&self) -> String {
+        self.name.clone()
+    }
+
+    #[getter(partitions)]
+    fn py_partitions(&self) -> Vec<String> {
+        self.partitions.clone()
+    }
+
+    #[getter(wildcard)]
+    fn py_wildcard(&self) -> bool {
+        self.wildcard
+    }
+
+    #[getter(parts)]
+    fn py_parts(&self) -> Vec<NamePart> {
+        self.parts.clone()
+    }
+
+    // Qualified name, quoted so that given dialect reads it back.
+    #[pyo3(name = "quoted_name")]
+    fn py_quoted_name(&self, dialect: Option<&str>) -> String {
+        self.quoted_name(get_generic_dialect(dialect).as_ref())
+    }
+
+    #[getter(original_name)]
+    fn py_original_name(&self) -> String {
+        self.original_name.clone()
+    }
+
+    #[getter(qualified_name)]
+    fn py_qualified_name(&self) -> String {
+        self.qualified_name()
+    }
+
+    fn __richcmp__(&self, other: &Self, op: CompareOp) -> PyResult<bool> {
+        match op {
+            CompareOp::Eq => Ok(self.qualified_name() == other.qualified_name()),
+            CompareOp::Ne => Ok(self.qualified_name() != other.qualified_name()),
+            _ => Err(PyTypeError::new_err(format!(
+                "can't use operator {op:?} on DbTableMeta"
+            ))),
+        }
+    }
+
+    fn __repr__(&self) -> String {
+        self.qualified_name()
+    }
+
+    fn __str__(&self) -> String {
+        self.__repr__()
+    }
+
+    fn __hash__(&self) -> isize {
+        let mut hasher = DefaultHasher::new();
+        self.hash(&mut hasher);
+        hasher.finish() as isize
+    }
+}
+
+#[pymethods]
+impl NamePart {
+    #[getter(value)]
+    fn py_value(&self) -> String {
+        self.value.clone()
+    }
+
+    #[getter(quote_style)]
+    fn py_quote_style(&self) -> Option<String> {
+        self.quote_style.map(String::from)
+    }
+
+    #[getter(raw)]
+    fn py_raw(&self) -> String {
+        self.raw()
+    }
+
+    fn __repr__(&self) -> String {
+        self.raw()
+    }
+
+    fn __str__(&self) -> String {
+        self.__repr__()
+    }
+}
+
+#[pymethods]
+impl ColumnMeta {
+    #[getter(origin)]
+    fn py_origin(&self) -> Option<DbTableMeta> {
+        self.origin.clone()
+    }
+
+    #[getter(name)]
+    fn py_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn __repr__(&self) -> String {
+        match &self.origin {
+            Some(table) => format!("{}.{}", table.qualified_name(), self.name),
+            None => self.name.clone(),
+        }
+    }
+
+    fn __str__(&self) -> String {
+        self.__repr__()
+    }
+}
+
+#[pymethods]
+impl ColumnLineage {
+    #[getter(descendant)]
+    fn py_descendant(&self) -> ColumnMeta {
+        self.descendant.clone()
+    }
+
+    #[getter(lineage)]
+    fn py_lineage(&self) -> Vec<ColumnMeta> {
+        self.lineage.clone()
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "{{\"descendant\": {:?}, \"lineage\": {:?} }}",
+            self.descendant, self.lineage
+        )
+    }
+
+    fn __str__(&self) -> String {
+        self.__repr__()
+    }
+}
+
+#[pymethods]
+impl DatasetOperation {
+    #[getter(table)]
+    fn py_table(&self) -> DbTableMeta {
+        self.table.clone()
+    }
+
+    #[getter(operation)]
+    fn py_operation(&self) -> &'static str {
+        self.operation.as_str()
+    }
+
+    fn __repr__(&self) -> String {
+        format!("{}: {}", self.table.qualified_name(), self.operation)
+    }
+
+    fn __str__(&self) -> String {
+        self.__repr__()
+    }
+}
+
+#[pymethods]
+impl LifecycleChange {
+    #[getter(statement_index)]
+    fn py_statement_index(&self) -> usize {
+        self.statement_index
+    }
+
+    #[getter(table)]
+    fn py_table(&self) -> DbTableMeta {
+        self.table.clone()
+    }
+
+    #[getter(operation)]
+    fn py_operation(&self) -> &'static str {
+        self.operation.as_str()
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "{}: {} {}",
+            self.statement_index,
+            self.table.qualified_name(),
+            self.operation
+        )
+    }
+
+    fn __str__(&self) -> String {
+        self.__repr__()
+    }
+}
+
+// `from` is a keyword in Python, so both ends are suffixed.
+#[pymethods]
+impl TableRename {
+    #[getter(from_table)]
+    fn py_from_table(&self) -> DbTableMeta {
+        self.from.clone()
+    }
+
+    #[getter(to_table)]
+    fn py_to_table(&self) -> DbTableMeta {
+        self.to.clone()
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "{} -> {}",
+            self.from.qualified_name(),
+            self.to.qualified_name()
+        )
+    }
+
+    fn __str__(&self) -> String {
+        self.__repr__()
+    }
+}
+
+#[pymethods]
+impl Location {
+    #[getter(line)]
+    fn py_line(&self) -> u64 {
+        self.line
+    }
+
+    #[getter(column)]
+    fn py_column(&self) -> u64 {
+        self.column
+    }
+
+    #[getter(offset)]
+    fn py_offset(&self) -> usize {
+        self.offset
+    }
+
+    fn __repr__(&self) -> String {
+        format!("{}:{}", self.line, self.column)
+    }
+
+    fn __str__(&self) -> String {
+        self.__repr__()
+    }
+}
+
+#[pymethods]
+impl TableOccurrence {
+    #[getter(table)]
+    fn py_table(&self) -> DbTableMeta {
+        self.table.clone()
+    }
+
+    #[getter(role)]
+    fn py_role(&self) -> &'static str {
+        self.role.as_str()
+    }
+
+    #[getter(statement_index)]
+    fn py_statement_index(&self) -> usize {
+        self.statement_index
+    }
+
+    #[getter(start)]
+    fn py_start(&self) -> Location {
+        self.start
+    }
+
+    #[getter(end)]
+    fn py_end(&self) -> Location {
+        self.end
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "{} {} at {}:{}",
+            self.role.as_str(),
+            self.table.qualified_name(),
+            self.start.line,
+            self.start.column
+        )
+    }
+
+    fn __str__(&self) -> String {
+        self.__repr__()
+    }
+}
+
+#[pymethods]
+impl ExtractionError {
+    #[getter(index)]
+    fn py_index(&self) -> usize {
+        self.index
+    }
+
+    #[getter(statement)]
+    fn py_statement(&self) -> String {
+        self.statement.clone()
+    }
+
+    #[getter(reason)]
+    fn py_reason(&self) -> String {
+        self.reason.to_string()
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "{{\"index\": {}, \"statement\": {:?}, \"reason\": {:?} }}",
+            self.index,
+            self.statement,
+            self.reason.to_string()
+        )
+    }
+
+    fn __str__(&self) -> String {
+        self.__repr__()
+    }
+}
+
+#[pymethods]
+impl SqlMeta {
+    #[getter(in_tables)]
+    fn py_in_tables(&self) -> Vec<DbTableMeta> {
+        self.in_tables.clone()
+    }
+
+    #[getter(out_tables)]
+    fn py_out_tables(&self) -> Vec<DbTableMeta> {
+        self.out_tables.clone()
+    }
+
+    #[getter(column_lineage)]
+    fn py_column_lineage(&self) -> Vec<ColumnLineage> {
+        self.column_lineage.clone()
+    }
+
+    #[getter(operations)]
+    fn py_operations(&self) -> Vec<DatasetOperation> {
+        self.operations.clone()
+    }
+
+    #[getter(occurrences)]
+    fn py_occurrences(&self) -> Vec<TableOccurrence> {
+        self.occurrences.clone()
+    }
+
+    #[getter(renames)]
+    fn py_renames(&self) -> Vec<TableRename> {
+        self.renames.clone()
+    }
+
+    #[getter(lifecycle_changes)]
+    fn py_lifecycle_changes(&self) -> Vec<LifecycleChange> {
+        self.lifecycle_changes.clone()
+    }
+
+    #[getter(errors)]
+    fn py_errors(&self) -> Vec<ExtractionError> {
+        self.errors.clone()
+    }
+
+    // Returns serialized columnLineage facet for each output table, keyed by its qualified name.
+    #[pyo3(name = "column_lineage_facets")]
+    fn py_column_lineage_facets(
+        &self,
+        namespace: &str,
+        producer: Option<&str>,
+    ) -> HashMap<String, String> {
+        self.column_lineage_facets(namespace, producer.unwrap_or(PRODUCER))
+            .into_iter()
+            .map(|(table, facet)| (table.qualified_name(), facet.to_json()))
+            .collect()
+    }
+
+    // Returns serialized lifecycleStateChange facet for each output table whose lifecycle
+    // state changed, keyed by its qualified name.
+    #[pyo3(name = "lifecycle_state_change_facets")]
+    fn py_lifecycle_state_change_facets(
+        &self,
+        namespace: &str,
+        producer: Option<&str>,
+    ) -> HashMap<String, String> {
+        self.lifecycle_state_change_facets(namespace, producer.unwrap_or(PRODUCER))
+            .into_iter()
+            .map(|(table, facet)| (table.qualified_name(), facet.to_json()))
+            .collect()
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "{{\"in_tables\": {:?}, \"out_tables\": {:?}, \"column_lineage\": {:?} }}",
+            self.in_tables, self.out_tables, self.column_lineage
+        )
+    }
+
+    fn __str__(&self) -> String {
+        self.__repr__()
+    }
+}
+
+#[pymethods]
+impl StatementMeta {
+    #[getter(index)]
+    fn py_index(&self) -> usize {
+        self.index
+    }
+
+    #[getter(statement)]
+    fn py_statement(&self) -> String {
+        self.statement.clone()
+    }
+
+    #[getter(in_tables)]
+    fn py_in_tables(&self) -> Vec<DbTableMeta> {
+        self.sql_meta.in_tables.clone()
+    }
+
+    #[getter(out_tables)]
+    fn py_out_tables(&self) -> Vec<DbTableMeta> {
+        self.sql_meta.out_tables.clone()
+    }
+
+    #[getter(column_lineage)]
+    fn py_column_lineage(&self) -> Vec<ColumnLineage> {
+        self.sql_meta.column_lineage.clone()
+    }
+
+    #[getter(errors)]
+    fn py_errors(&self) -> Vec<ExtractionError> {
+        self.sql_meta.errors.clone()
+    }
+
+    #[getter(temporary_tables)]
+    fn py_temporary_tables(&self) -> Vec<DbTableMeta> {
+        self.temporary_tables.clone()
+    }
+
+    #[getter(table_edges)]
+    fn py_table_edges(&self) -> Vec<(DbTableMeta, DbTableMeta)> {
+        self.table_edges()
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "{{\"index\": {}, \"statement\": {:?}, \"in_tables\": {:?}, \"out_tables\": {:?} }}",
+            self.index, self.statement, self.sql_meta.in_tables, self.sql_meta.out_tables
+        )
+    }
+
+    fn __str__(&self) -> String {
+        self.__repr__()
+    }
+}
+
+#[pymethods]
+impl Connection {
+    #[new]
+    #[allow(clippy::too_many_arguments)]
+    fn py_new(
+        scheme: &str,
+        host: Option<String>,
+        port: Option<u16>,
+        account: Option<String>,
+        project: Option<String>,
+        region: Option<String>,
+        database: Option<String>,
+    ) -> Self {
+        Connection {
+            host,
+            port,
+            account,
+            project,
+            region,
+            database,
+            ..Connection::new(scheme)
+        }
+    }
+
+    #[pyo3(name = "namespace")]
+    fn py_namespace(&self) -> PyResult<String> {
+        self.namespace().map_err(PyValueError::new_err)
+    }
+
+    #[pyo3(name = "dataset_name")]
+    fn py_dataset_name(&self, table: &DbTableMeta) -> PyResult<DatasetName> {
+        self.dataset_name(table).map_err(PyValueError::new_err)
+    }
+
+    fn __repr__(&self) -> String {
+        format!("{:?}", self)
+    }
+}
+
+#[pymethods]
+impl DatasetName {
+    #[getter(namespace)]
+    fn py_namespace(&self) -> String {
+        self.namespace.clone()
+    }
+
+    #[getter(name)]
+    fn py_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn __repr__(&self) -> String {
+        format!("{}/{}", self.namespace, self.name)
+    }
+
+    fn __str__(&self) -> String {
+        self.__repr__()
+    }
+}
+
+// Patterns of excluded tables are globs, or regexes if prefixed with `regex:`. Name mapping
+// is JSON document, in format NameMapping::from_json reads.
+#[allow(clippy::too_many_arguments)]
+fn parse_options(
+    default_schema: Option<&str>,
+    default_database: Option<&str>,
+    search_path: Option<Vec<String>>,
+    schema: Option<HashMap<String, Vec<String>>>,
+    tolerant: Option<bool>,
+    keep_temporary_tables: Option<bool>,
+    include_system_tables: Option<bool>,
+    exclude: Option<Vec<String>>,
+    name_mapping: Option<&str>,
+) -> PyResult<ParseOptions> {
+    let exclude = exclude
+        .unwrap_or_default()
+        .iter()
+        .map(|pattern| pattern.parse::<TablePattern>())
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(PyValueError::new_err)?;
+    let name_mapping = name_mapping
+        .map(NameMapping::from_json)
+        .transpose()
+        .map_err(PyValueError::new_err)?;
+    Ok(ParseOptions {
+        default_schema: default_schema.map(String::from),
+        search_path: search_path.unwrap_or_default(),
+        default_database: default_database.map(String::from),
+        schema_provider: schema
+            .map(|s| Arc::new(InMemorySchemaProvider::from(s)) as Arc<dyn SchemaProvider>),
+        tolerant: tolerant.unwrap_or(false),
+        keep_temporary_tables: keep_temporary_tables.unwrap_or(false),
+        include_system_tables: include_system_tables.unwrap_or(false),
+        exclude,
+        name_mapping: name_mapping.map(Arc::new),
+    })
+}
+
+// Parses SQL. Schema, if passed, maps qualified table names to lists of their columns.
+// In tolerant mode, statements that fail are skipped and reported in SqlMeta.errors.
+// Temporary tables are replaced by their sources, unless keep_temporary_tables is set.
+// Tables are qualified with default_database, and with the first schema of search_path
+// that has them, falling back to default_schema. System tables of the dialect, and tables
+// matching exclude patterns, are left out unless include_system_tables is set. Names of
+// tables are then rewritten by name_mapping, if passed.
+#[pyfunction]
+#[allow(clippy::too_many_arguments)]
+fn parse(
+    sql: Vec<&str>,
+    dialect: Option<&str>,
+    default_schema: Option<&str>,
+    schema: Option<HashMap<String, Vec<String>>>,
+    tolerant: Option<bool>,
+    keep_temporary_tables: Option<bool>,
+    default_database: Option<&str>,
+    search_path: Option<Vec<String>>,
+    include_system_tables: Option<bool>,
+    exclude: Option<Vec<String>>,
+    name_mapping: Option<&str>,
+) -> PyResult<SqlMeta> {
+    Ok(parse_multiple_statements_with_options(
+        sql,
+        get_generic_dialect(dialect),
+        &parse_options(
+            default_schema,
+            default_database,
+            search_path,
+            schema,
+            tolerant,
+            keep_temporary_tables,
+            include_system_tables,
+            exclude,
+            name_mapping,
+        )?,
+    )?)
+}
+
+// Same as parse, but returns lineage of each statement separately.
+#[pyfunction]
+#[allow(clippy::too_many_arguments)]
+fn parse_statements(
+    sql: Vec<&str>,
+    dialect: Option<&str>,
+    default_schema: Option<&str>,
+    schema: Option<HashMap<String, Vec<String>>>,
+    tolerant: Option<bool>,
+    default_database: Option<&str>,
+    search_path: Option<Vec<String>>,
+    include_system_tables: Option<bool>,
+    exclude: Option<Vec<String>>,
+    name_mapping: Option<&str>,
+) -> PyResult<Vec<StatementMeta>> {
+    Ok(parse_statements_with_options(
+        sql,
+        get_generic_dialect(dialect),
+        &parse_options(
+            default_schema,
+            default_database,
+            search_path,
+            schema,
+            tolerant,
+            None,
+            include_system_tables,
+            exclude,
+            name_mapping,
+        )?,
+    )?)
+}
+
+#[pyfunction]
+fn provider() -> String {
+    "rust".to_string()
+}
+
+/// A Python module implemented in Rust.
+#[pymodule]
+fn openlineage_sql(py: Python, m: &PyModule) -> PyResult<()> {
+    m.add_function(wrap_pyfunction!(parse, m)?)?;
+    m.add_function(wrap_pyfunction!(parse_statements, m)?)?;
+    m.add_function(wrap_pyfunction!(provider, m)?)?;
+    m.add_class::<SqlMeta>()?;
+    m.add_class::<DbTableMeta>()?;
+    m.add_class::<NamePart>()?;
+    m.add_class::<ColumnMeta>()?;
+    m.add_class::<ColumnLineage>()?;
+    m.add_class::<DatasetOperation>()?;
+    m.add_class::<TableOccurrence>()?;
+    m.add_class::<TableRename>()?;
+    m.add_class::<LifecycleChange>()?;
+    m.add_class::<Location>()?;
+    m.add_class::<ExtractionError>()?;
+    m.add_class::<StatementMeta>()?;
+    m.add_class::<Connection>()?;
+    m.add_class::<DatasetName>()?;
+    m.add("SqlParseError", py.get_type::<SqlParseError>())?;
+    m.add("SqlSyntaxError", py.get_type::<SqlSyntaxError>())?;
+    m.add("UnsupportedSqlError", py.get_type::<UnsupportedSqlError>())?;
+    m.add("EmptySqlError", py.get_type::<EmptySqlError>())?;
+    Ok(())
+}
@@ -0,0 +1,157 @@
+// Copyright 2018-2022 contributors to the OpenLineage project
+// SPDX-License-Identifier: Apache-2.0
+
+// SQL Server reads data from other servers with OPENQUERY and OPENROWSET, which run query,
+// passed as a string, on the remote server. Tables that query reads are reported as inputs,
+// qualified with the server they are on.
+
+use sqlparser::ast::{
+    Expr, FunctionArg, FunctionArgExpr, Ident, ObjectName, Statement, TableAlias, Value,
+};
+
+use crate::lineage::{ColumnMeta, OutputColumn, Relation};
+use crate::session::Session;
+use crate::{parse_query, tokens, Context, DbTableMeta, ParseError, ParseOptions, TableRole};
+
+// Relation read by table-valued function, if it's one that reads from remote server.
+pub(crate) fn parse_remote_source(
+    name: &ObjectName,
+    args: &[FunctionArg],
+    alias: Option<&TableAlias>,
+    context: &mut Context,
+) -> Result<Option<Relation>, ParseError> {
+    let function = match name.0.as_slice() {
+        [function] if function.quote_style.is_none() => function.value.to_uppercase(),
+        _ => return Ok(None),
+    };
+    if function != "OPENQUERY" && function != "OPENROWSET" {
+        return Ok(None);
+    }
+    let args: Vec<&Expr> = args
+        .iter()
+        .filter_map(|arg| match arg {
+            FunctionArg::Unnamed(FunctionArgExpr::Expr(expr)) => Some(expr),
+            _ => None,
+        })
+        .collect();
+    let alias_name = alias.map(|a| a.name.value.clone());
+    let source = match (function.as_str(), args.as_slice()) {
+        // OPENQUERY(linked_server, 'query')
+        ("OPENQUERY", [Expr::Identifier(server), source]) => {
+            Some((context.dialect.canonical_name(server), *source))
+        }
+        // OPENROWSET('provider', 'Server=host;...', 'query' | table)
+        ("OPENROWSET", [_, Expr::Value(Value::SingleQuotedString(connection)), source]) => {
+            connection_server(connection).map(|server| (server, *source))
+        }
+        _ => None,
+    };
+    let relation = match source {
+        Some((server, Expr::Value(Value::SingleQuotedString(sql)))) => {
+            parse_remote_query(sql, &server, context)?
+                .map(|columns| Relation::derived(columns, alias_name.clone()))
+        }
+        Some((server, Expr::Identifier(ident))) => Some(remote_table(
+            std::slice::from_ref(ident),
+            &server,
+            alias,
+            context,
+        )?),
+        Some((server, Expr::CompoundIdentifier(idents))) => {
+            Some(remote_table(idents, &server, alias, context)?)
+        }
+        _ => None,
+    };
+    match relation {
+        Some(relation) => Ok(Some(relation)),
+        None => {
+            context.skip_unsupported(ParseError::Unsupported {
+                node: format!("TableFactor::{}", function),
+                message: format!("Can't tell what {} reads from: {}", function, name),
+            })?;
+            Ok(Some(Relation::derived(vec![], alias_name)))
+        }
+    }
+}
+
+// Parses query run on remote server. Session of the script doesn't apply there, so its tables
+// are qualified only as much as query itself qualifies them. None if it's not a query.
+// Query that can't be parsed is read as one without columns, if parsing is tolerant.
+fn parse_remote_query(
+    sql: &str,
+    server: &str,
+    context: &mut Context,
+) -> Result<Option<Vec<OutputColumn>>, ParseError> {
+    let options = ParseOptions {
+        tolerant: context.tolerant,
+        include_system_tables: true,
+        ..ParseOptions::default()
+    };
+    let session = Session::new(context.dialect.as_ref(), &options);
+    let mut remote = Context::new(context.dialect.clone(), &options, &session);
+    let statement = tokens::tokenize(context.dialect.as_base(), sql)
+        .and_then(|tokens| tokens::parse_statement(context.dialect.as_base(), &tokens));
+    let query = match statement {
+        Ok(Statement::Query(query)) => query,
+        Ok(_) => return Ok(None),
+        Err(error) => {
+            context.skip_unsupported(error)?;
+            return Ok(Some(vec![]));
+        }
+    };
+    let columns = parse_query(&query, &mut remote)?;
+
+    context.errors.extend(remote.errors);
+    for table in remote.inputs {
+        context.insert_input(on_server(table, server));
+    }
+    Ok(Some(
+        columns
+            .into_iter()
+            .map(|column| {
+                let sources = column
+                    .sources
+                    .into_iter()
+                    .map(|source| ColumnMeta {
+                        origin: source.origin.map(|table| on_server(table, server)),
+                        ..source
+                    })
+                    .collect();
+                OutputColumn::new(column.name, sources)
+            })
+            .collect(),
+    ))
+}
+
+fn remote_table(
+    idents: &[Ident],
+    server: &str,
+    alias: Option<&TableAlias>,
+    context: &mut Context,
+) -> Result<Relation, ParseError> {
+    let name = ObjectName(idents.to_vec());
+    let table =
+        DbTableMeta::from_idents(idents, name.to_string(), context.dialect.as_ref(), false)?;
+    let table = on_server(table, server);
+    context.add_reference(idents, &table, TableRole::Input);
+    context.insert_input(table.clone());
+    Ok(Relation::table(table, None, alias))
+}
+
+fn on_server(table: DbTableMeta, server: &str) -> DbTableMeta {
+    DbTableMeta {
+        server: table.server.or_else(|| Some(String::from(server))),
+        ..table
+    }
+}
+
+// Server that OLE DB connection string, like `Server=host;Trusted_Connection=yes;`, points to.
+fn connection_server(connection: &str) -> Option<String> {
+    connection.split(';').find_map(|setting| {
+        let (key, value) = setting.split_once('=')?;
+        match key.trim().to_lowercase().as_str() {
+            "server" | "data source" | "address" | "addr" => Some(value.trim().to_string()),
+            _ => None,
+        }
+    })
+}
@@ -0,0 +1,112 @@
+// Copyright 2018-2022 contributors to the OpenLineage project
+// SPDX-License-Identifier: Apache-2.0
+
+// Statements that rename tables, which parser doesn't know: Snowflake's
+// `ALTER TABLE a SWAP WITH b`, Postgres' `ALTER TABLE a SET SCHEMA s`, MySQL's
+// `RENAME TABLE a TO b, ...` and T-SQL's `EXEC sp_rename 'a', 'b'`. They are recognized
+// from tokens, like session commands are. `ALTER TABLE a RENAME TO b` is parsed as usual.
+
+use sqlparser::ast::{Ident, ObjectName};
+use sqlparser::dialect::Dialect;
+use sqlparser::tokenizer::Token;
+
+use crate::tokens::{self, is_keyword, split_name, LocatedToken};
+
+// Old and new name of each table renamed by the statement, if it's one of statements above.
+// Statement that renames something else than table, like column, renames nothing.
+pub(crate) fn parse_rename(
+    dialect: &dyn Dialect,
+    tokens: &[LocatedToken],
+) -> Option<Vec<(ObjectName, ObjectName)>> {
+    let tokens: Vec<&Token> = tokens
+        .iter()
+        .filter(|t| !t.is_whitespace())
+        .map(|t| &t.token)
+        .collect();
+    match tokens.as_slice() {
+        [alter, table, rest @ ..] if is_keyword(alter, "ALTER") && is_keyword(table, "TABLE") => {
+            parse_alter_table(rest)
+        }
+        [rename, table, rest @ ..]
+            if is_keyword(rename, "RENAME") && is_keyword(table, "TABLE") =>
+        {
+            parse_rename_table(rest)
+        }
+        [exec, rest @ ..] if is_keyword(exec, "EXEC") || is_keyword(exec, "EXECUTE") => {
+            parse_sp_rename(dialect, rest)
+        }
+        _ => parse_sp_rename(dialect, &tokens),
+    }
+}
+
+// `ALTER TABLE [IF EXISTS] a SWAP WITH b` and `ALTER TABLE [IF EXISTS] a SET SCHEMA s`.
+fn parse_alter_table(tokens: &[&Token]) -> Option<Vec<(ObjectName, ObjectName)>> {
+    let tokens = match tokens {
+        [if_, exists, rest @ ..] if is_keyword(if_, "IF") && is_keyword(exists, "EXISTS") => rest,
+        _ => tokens,
+    };
+    let (name, rest) = split_name(tokens)?;
+    match rest {
+        [swap, with, other @ ..] if is_keyword(swap, "SWAP") && is_keyword(with, "WITH") => {
+            let other = ObjectName(tokens::object_name(other)?);
+            Some(vec![(name.clone(), other.clone()), (other, name)])
+        }
+        [set, schema, Token::Word(word)]
+            if is_keyword(set, "SET") && is_keyword(schema, "SCHEMA") =>
+        {
+            let table = name.0.last()?.clone();
+            let schema = Ident {
+                value: word.value.clone(),
+                quote_style: word.quote_style,
+            };
+            Some(vec![(name, ObjectName(vec![schema, table]))])
+        }
+        _ => None,
+    }
+}
+
+// `RENAME TABLE a TO b [, c TO d ...]`
+fn parse_rename_table(tokens: &[&Token]) -> Option<Vec<(ObjectName, ObjectName)>> {
+    tokens
+        .split(|token| **token == Token::Comma)
+        .map(|pair| {
+            let (old, rest) = split_name(pair)?;
+            match rest {
+                [to, new @ ..] if is_keyword(to, "TO") => {
+                    Some((old, ObjectName(tokens::object_name(new)?)))
+                }
+                _ => None,
+            }
+        })
+        .collect()
+}
+
+// `sp_rename 'schema.old', 'new' [, 'OBJECT']`, with procedure name optionally qualified.
+fn parse_sp_rename(
+    dialect: &dyn Dialect,
+    tokens: &[&Token],
+) -> Option<Vec<(ObjectName, ObjectName)>> {
+    let (procedure, rest) = split_name(tokens)?;
+    match procedure.0.last() {
+        Some(ident) if ident.value.eq_ignore_ascii_case("sp_rename") => {}
+        _ => return None,
+    }
+    let args: Vec<&str> = rest
+        .split(|token| **token == Token::Comma)
+        .map(|arg| match arg {
+            [Token::SingleQuotedString(s)] | [Token::NationalStringLiteral(s)] => Some(s.as_str()),
+            _ => None,
+        })
+        .collect::<Option<_>>()?;
+    let (old, new) = match args.as_slice() {
+        [old, new] => (old, new),
+        [old, new, kind] if kind.eq_ignore_ascii_case("OBJECT") => (old, new),
+        // Columns, indexes and other objects aren't tables.
+        [_, _, _] => return Some(vec![]),
+        _ => return None,
+    };
+    Some(vec![(
+        ObjectName(tokens::parse_object_name(dialect, old)?),
+        ObjectName(tokens::parse_object_name(dialect, new)?),
+    )])
+}
@@ -0,0 +1,131 @@
+// Copyright 2018-2022 contributors to the OpenLineage project
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::HashMap;
+use std::fmt::Debug;
+use std::fs;
+use std::path::Path;
+
+use crate::DbTableMeta;
+
+/// Source of table metadata the parser can't get from SQL text alone.
+pub trait SchemaProvider: Debug {
+    /// Returns column names of the table, in their order, or None if table is unknown.
+    fn get_columns(&self, table: &DbTableMeta) -> Option<Vec<String>>;
+
+    /// Resolves table name that lacks database or schema to a fully qualified one.
+    /// Returns None if table is unknown, or if name is ambiguous.
+    fn resolve_table(&self, table: &DbTableMeta) -> Option<DbTableMeta>;
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct InMemorySchemaProvider {
+    tables: HashMap<DbTableMeta, Vec<String>>,
+}
+
+impl InMemorySchemaProvider {
+    pub fn new() -> Self {
+        InMemorySchemaProvider::default()
+    }
+
+    pub fn add_table(&mut self, table: DbTableMeta, columns: Vec<String>) {
+        self.tables.insert(table, columns);
+    }
+}
+
+impl From<HashMap<String, Vec<String>>> for InMemorySchemaProvider {
+    // Keys are qualified table names, like `database.schema.table`
+    fn from(tables: HashMap<String, Vec<String>>) -> Self {
+        InMemorySchemaProvider {
+            tables: tables
+                .into_iter()
+                .map(|(name, columns)| (DbTableMeta::new_default_dialect(name), columns))
+                .collect(),
+        }
+    }
+}
+
+// Names in schema may be spelled in different case than dialect folds identifiers to,
+// so they are compared case insensitively.
+fn same_name(name: &str, known: &str) -> bool {
+    name.eq_ignore_ascii_case(known)
+}
+
+// Part of the name schema doesn't specify matches any, so that schema listing tables as
+// `schema.table` still works when queries name their database.
+fn part_matches(part: &Option<String>, known: &Option<String>) -> bool {
+    match (part, known) {
+        (None, _) | (_, None) => true,
+        (Some(part), Some(known)) => same_name(part, known),
+    }
+}
+
+impl InMemorySchemaProvider {
+    // The only known table name can refer to.
+    fn find(&self, table: &DbTableMeta) -> Option<(&DbTableMeta, &Vec<String>)> {
+        if let Some(entry) = self.tables.get_key_value(table) {
+            return Some(entry);
+        }
+        let mut candidates = self.tables.iter().filter(|(known, _)| {
+            same_name(&table.name, &known.name)
+                && part_matches(&table.schema, &known.schema)
+                && part_matches(&table.database, &known.database)
+                && part_matches(&table.server, &known.server)
+        });
+        match (candidates.next(), candidates.next()) {
+            (Some(entry), None) => Some(entry),
+            _ => None,
+        }
+    }
+}
+
+impl SchemaProvider for InMemorySchemaProvider {
+    fn get_columns(&self, table: &DbTableMeta) -> Option<Vec<String>> {
+        self.find(table).map(|(_, columns)| columns.clone())
+    }
+
+    fn resolve_table(&self, table: &DbTableMeta) -> Option<DbTableMeta> {
+        // Parts the name has are kept as they are, since they match the schema's regardless
+        // of case. Schema's spelling is left only in the original name.
+        self.find(table).map(|(known, _)| DbTableMeta {
+            server: table.server.clone().or_else(|| known.server.clone()),
+            database: table.database.clone().or_else(|| known.database.clone()),
+            schema: table.schema.clone().or_else(|| known.schema.clone()),
+            original_name: known.original_name.clone(),
+            ..table.clone()
+        })
+    }
+}
+
+/// Reads table metadata from JSON file that maps qualified table names to lists of columns:
+/// `{"db.public.orders": ["id", "customer_id", "amount"]}`
+#[derive(Debug, Clone)]
+pub struct JsonSchemaProvider {
+    inner: InMemorySchemaProvider,
+}
+
+impl JsonSchemaProvider {
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Self, String> {
+        let content = fs::read_to_string(path.as_ref())
+            .map_err(|e| format!("can't read schema file {}: {}", path.as_ref().display(), e))?;
+        JsonSchemaProvider::from_json(&content)
+    }
+
+    pub fn from_json(content: &str) -> Result<Self, String> {
+        let tables: HashMap<String, Vec<String>> =
+            serde_json::from_str(content).map_err(|e| format!("can't parse schema file: {}", e))?;
+        Ok(JsonSchemaProvider {
+            inner: InMemorySchemaProvider::from(tables),
+        })
+    }
+}
+
+impl SchemaProvider for JsonSchemaProvider {
+    fn get_columns(&self, table: &DbTableMeta) -> Option<Vec<String>> {
+        self.inner.get_columns(table)
+    }
+
+    fn resolve_table(&self, table: &DbTableMeta) -> Option<DbTableMeta> {
+        self.inner.resolve_table(table)
+    }
+}
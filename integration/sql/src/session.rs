@@ -0,0 +1,208 @@
+// Copyright 2018-2022 contributors to the OpenLineage project
+// SPDX-License-Identifier: Apache-2.0
+
+// Scripts often start with commands that change current database or schema, like
+// `USE DATABASE analytics` or `SET search_path TO staging`, and later statements refer
+// to tables relative to them. Parser doesn't know most of those commands, so they are
+// recognized from tokens, before statement is parsed.
+
+use std::collections::HashMap;
+
+use sqlparser::ast::Ident;
+use sqlparser::dialect::{HiveDialect, MsSqlDialect, MySqlDialect, SnowflakeDialect};
+use sqlparser::tokenizer::Token;
+
+use crate::lineage::OutputColumn;
+use crate::tokens::{self, is_keyword, LocatedToken};
+use crate::{CanonicalDialect, DbTableMeta, ParseOptions};
+
+// Database and schemas unqualified table names are resolved against at given point of script.
+#[derive(Debug, Clone)]
+pub(crate) struct Session {
+    pub database: Option<String>,
+    pub search_path: Vec<String>,
+    // Search path set by caller, restored by `SET search_path TO DEFAULT`.
+    default_search_path: Vec<String>,
+    // Views created earlier in the script, so that queries reading them can be traced
+    // to tables they are defined on.
+    pub views: HashMap<DbTableMeta, ViewDefinition>,
+}
+
+// What view created in the script reads: columns it's made of, with their sources,
+// and its base tables.
+#[derive(Debug, Clone)]
+pub(crate) struct ViewDefinition {
+    pub columns: Vec<OutputColumn>,
+    pub inputs: Vec<DbTableMeta>,
+}
+
+impl Session {
+    // Names in options are folded like unquoted identifiers of the dialect, so that they match
+    // names read from SQL, for example `prod` is `PROD` in Snowflake.
+    pub fn new(dialect: &dyn CanonicalDialect, options: &ParseOptions) -> Self {
+        let fold = |name: &String| dialect.identifier_case().fold(name, false);
+        let mut database = options.default_database.as_ref().map(fold);
+        let mut search_path: Vec<String> = if options.search_path.is_empty() {
+            options.default_schema.iter().map(fold).collect()
+        } else {
+            options.search_path.iter().map(fold).collect()
+        };
+        // In MySQL and Hive database is what other dialects call schema.
+        if dialect.as_base().is::<MySqlDialect>() || dialect.as_base().is::<HiveDialect>() {
+            if let Some(database) = database.take() {
+                if search_path.is_empty() {
+                    search_path = vec![database];
+                }
+            }
+        }
+        Session {
+            database,
+            search_path: search_path.clone(),
+            default_search_path: search_path,
+            views: HashMap::new(),
+        }
+    }
+
+    // If statement is a command that changes session, applies it and returns true.
+    // Such statement has no lineage, so it doesn't have to be parsed.
+    pub fn apply(&mut self, dialect: &dyn CanonicalDialect, tokens: &[LocatedToken]) -> bool {
+        let tokens: Vec<&Token> = tokens
+            .iter()
+            .filter(|t| !t.is_whitespace())
+            .map(|t| &t.token)
+            .collect();
+        match tokens.as_slice() {
+            [first, rest @ ..] if is_keyword(first, "USE") => {
+                self.apply_use(dialect, rest);
+                true
+            }
+            [first, rest @ ..] if is_keyword(first, "SET") => self.apply_set(dialect, rest),
+            [alter, session, set, rest @ ..]
+                if is_keyword(alter, "ALTER")
+                    && is_keyword(session, "SESSION")
+                    && is_keyword(set, "SET") =>
+            {
+                self.apply_alter_session(dialect, rest);
+                true
+            }
+            _ => false,
+        }
+    }
+
+    // `USE [DATABASE | CATALOG | SCHEMA] name`. Other objects that can be used, like Snowflake
+    // warehouses and roles, don't affect table names.
+    fn apply_use(&mut self, dialect: &dyn CanonicalDialect, tokens: &[&Token]) {
+        let (kind, name) = match tokens {
+            [kind, name @ ..] if is_keyword(kind, "DATABASE") || is_keyword(kind, "CATALOG") => {
+                ("DATABASE", name)
+            }
+            [kind, name @ ..] if is_keyword(kind, "SCHEMA") => ("SCHEMA", name),
+            [kind, ..]
+                if is_keyword(kind, "WAREHOUSE")
+                    || is_keyword(kind, "ROLE")
+                    || is_keyword(kind, "SECONDARY") =>
+            {
+                return
+            }
+            // In MySQL and Hive database is what other dialects call schema.
+            name if dialect.as_base().is::<MySqlDialect>()
+                || dialect.as_base().is::<HiveDialect>() =>
+            {
+                ("SCHEMA", name)
+            }
+            name => ("DATABASE", name),
+        };
+        match (kind, object_name(dialect, name).as_deref()) {
+            (_, Some([database, schema])) => {
+                self.database = Some(database.clone());
+                self.search_path = vec![schema.clone()];
+            }
+            ("DATABASE", Some([database])) => {
+                self.database = Some(database.clone());
+                self.reset_search_path(dialect);
+            }
+            ("SCHEMA", Some([schema])) => self.search_path = vec![schema.clone()],
+            _ => {}
+        }
+    }
+
+    // Schema of previous database doesn't apply in the new one. Snowflake switches to PUBLIC
+    // schema of the database. SQL Server keeps default schema, as it belongs to the user.
+    fn reset_search_path(&mut self, dialect: &dyn CanonicalDialect) {
+        let dialect = dialect.as_base();
+        if dialect.is::<SnowflakeDialect>() {
+            self.search_path = vec![String::from("PUBLIC")];
+        } else if !dialect.is::<MsSqlDialect>() {
+            self.search_path.clear();
+        }
+    }
+
+    // `SET [SESSION | LOCAL] search_path TO schema, ...` and `SET SCHEMA 'schema'` of Postgres.
+    // Other variables are left to parser.
+    fn apply_set(&mut self, dialect: &dyn CanonicalDialect, tokens: &[&Token]) -> bool {
+        let tokens = match tokens {
+            [scope, rest @ ..] if is_keyword(scope, "SESSION") || is_keyword(scope, "LOCAL") => {
+                rest
+            }
+            _ => tokens,
+        };
+        match tokens {
+            [variable, assign, values @ ..]
+                if is_keyword(variable, "search_path")
+                    && (**assign == Token::Eq || is_keyword(assign, "TO")) =>
+            {
+                self.set_search_path(dialect, values);
+                true
+            }
+            [variable, value] if is_keyword(variable, "SCHEMA") => {
+                self.set_search_path(dialect, &[value]);
+                true
+            }
+            _ => false,
+        }
+    }
+
+    // `ALTER SESSION SET CURRENT_SCHEMA = schema` of Oracle. Other parameters, like Snowflake's
+    // `QUERY_TAG`, don't affect table names.
+    fn apply_alter_session(&mut self, dialect: &dyn CanonicalDialect, tokens: &[&Token]) {
+        for parameter in tokens.windows(3) {
+            if let [name, Token::Eq, value] = parameter {
+                if is_keyword(name, "CURRENT_SCHEMA") {
+                    self.set_search_path(dialect, &[value]);
+                }
+            }
+        }
+    }
+
+    fn set_search_path(&mut self, dialect: &dyn CanonicalDialect, values: &[&Token]) {
+        if let [value] = values {
+            if is_keyword(value, "DEFAULT") {
+                self.search_path = self.default_search_path.clone();
+                return;
+            }
+        }
+        self.search_path = values
+            .iter()
+            .filter_map(|value| match value {
+                Token::Word(word) => Some(dialect.canonical_name(&Ident {
+                    value: word.value.clone(),
+                    quote_style: word.quote_style,
+                })),
+                Token::SingleQuotedString(value) => Some(value.clone()),
+                _ => None,
+            })
+            // Variables, like `$user`, depend on who runs the script.
+            .filter(|schema| !schema.starts_with('$'))
+            .collect();
+    }
+}
+
+// Parts of dot-separated name, if tokens are exactly that.
+fn object_name(dialect: &dyn CanonicalDialect, tokens: &[&Token]) -> Option<Vec<String>> {
+    tokens::object_name(tokens).map(|idents| {
+        idents
+            .iter()
+            .map(|ident| dialect.canonical_name(ident))
+            .collect()
+    })
+}
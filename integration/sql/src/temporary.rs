@@ -0,0 +1,144 @@
+// Copyright 2018-2022 contributors to the OpenLineage project
+// SPDX-License-Identifier: Apache-2.0
+
+// Temporary tables are usually just intermediate steps of a script: data is loaded into them
+// and then read back into final tables. Here we attribute data that flows through temporary
+// table to tables it was filled from, so that script's lineage shows only persistent tables.
+
+use std::collections::{HashMap, HashSet};
+
+use crate::{
+    extend_tables, ColumnMeta, DatasetOperation, DbTableMeta, LifecycleChange, Operation, SqlMeta,
+    StatementMeta, TableOccurrence, TableRename, TableRole,
+};
+
+// Session-scoped tables, like `#orders` and `##orders` in MSSQL, are temporary
+// even if they aren't declared as such.
+pub(crate) fn is_session_table(table: &DbTableMeta) -> bool {
+    table.name.starts_with('#')
+}
+
+// Merges lineage of the statements, replacing temporary tables with their sources.
+// Statements have to be in order in which they are executed. Table is temporary from
+// the statement that creates it, or renames temporary table to it, until it's dropped.
+// Before and after that, the name may refer to persistent table.
+pub(crate) fn merge_without_temporary(statements: Vec<StatementMeta>) -> SqlMeta {
+    let mut temporary: HashSet<DbTableMeta> = HashSet::new();
+    // Tables and columns that data in temporary tables was computed from, so far.
+    let mut table_sources: HashMap<DbTableMeta, HashSet<DbTableMeta>> = HashMap::new();
+    let mut column_sources: HashMap<ColumnMeta, HashSet<ColumnMeta>> = HashMap::new();
+
+    let mut inputs: HashSet<DbTableMeta> = HashSet::new();
+    let mut outputs: HashSet<DbTableMeta> = HashSet::new();
+    let mut column_lineage: HashMap<ColumnMeta, HashSet<ColumnMeta>> = HashMap::new();
+    let mut operations: HashSet<DatasetOperation> = HashSet::new();
+    let mut occurrences: Vec<TableOccurrence> = vec![];
+    let mut renames: HashSet<TableRename> = HashSet::new();
+    let mut lifecycle_changes: Vec<LifecycleChange> = vec![];
+    let mut errors = vec![];
+
+    for statement in statements {
+        temporary.extend(statement.temporary_tables);
+        let meta = statement.sql_meta;
+        // Renamed temporary table stays temporary under its new name, with the same columns.
+        for rename in &meta.renames {
+            if temporary.contains(&rename.from) {
+                temporary.insert(rename.to.clone());
+                let renamed: Vec<(ColumnMeta, HashSet<ColumnMeta>)> = column_sources
+                    .iter()
+                    .filter(|(column, _)| column.origin.as_ref() == Some(&rename.from))
+                    .map(|(column, sources)| {
+                        let column = ColumnMeta::new(column.name.clone(), Some(rename.to.clone()));
+                        (column, sources.clone())
+                    })
+                    .collect();
+                column_sources.extend(renamed);
+            }
+        }
+
+        let mut statement_inputs: HashSet<DbTableMeta> = HashSet::new();
+        // Temporary tables read by the statement, which are replaced by their sources.
+        let mut replaced: HashSet<DbTableMeta> = HashSet::new();
+        for input in meta.in_tables {
+            // Temporary table that wasn't written in this script yet is kept as it is.
+            match table_sources.get(&input) {
+                Some(sources) => {
+                    extend_tables(&mut statement_inputs, sources.iter().cloned());
+                    replaced.insert(input);
+                }
+                None => extend_tables(&mut statement_inputs, [input]),
+            }
+        }
+        for output in meta.out_tables {
+            if temporary.contains(&output) {
+                extend_tables(
+                    table_sources.entry(output).or_default(),
+                    statement_inputs.iter().cloned(),
+                );
+            } else {
+                extend_tables(&mut outputs, [output]);
+            }
+        }
+        extend_tables(&mut inputs, statement_inputs);
+
+        for lineage in meta.column_lineage {
+            let mut sources: HashSet<ColumnMeta> = HashSet::new();
+            for source in lineage.lineage {
+                match column_sources.get(&source) {
+                    Some(s) => sources.extend(s.iter().cloned()),
+                    None => {
+                        sources.insert(source);
+                    }
+                }
+            }
+            let lineage_map = match &lineage.descendant.origin {
+                Some(table) if temporary.contains(table) => &mut column_sources,
+                _ => &mut column_lineage,
+            };
+            lineage_map
+                .entry(lineage.descendant)
+                .or_default()
+                .extend(sources);
+        }
+        operations.extend(
+            meta.operations
+                .iter()
+                .filter(|o| !temporary.contains(&o.table))
+                .cloned(),
+        );
+        occurrences.extend(meta.occurrences.into_iter().filter(|o| match o.role {
+            TableRole::Input => !replaced.contains(&o.table),
+            TableRole::Output => !temporary.contains(&o.table),
+        }));
+        renames.extend(
+            meta.renames
+                .into_iter()
+                .filter(|r| !temporary.contains(&r.from) && !temporary.contains(&r.to)),
+        );
+        lifecycle_changes.extend(
+            meta.lifecycle_changes
+                .into_iter()
+                .filter(|c| !temporary.contains(&c.table)),
+        );
+        errors.extend(meta.errors);
+
+        // Once temporary table is dropped, its name refers to persistent table again.
+        for operation in &meta.operations {
+            if operation.operation == Operation::Drop && temporary.remove(&operation.table) {
+                table_sources.remove(&operation.table);
+                column_sources.retain(|column, _| column.origin.as_ref() != Some(&operation.table));
+            }
+        }
+    }
+
+    SqlMeta::new(
+        inputs.into_iter().collect(),
+        outputs.into_iter().collect(),
+        column_lineage,
+        operations,
+        occurrences,
+        renames,
+        lifecycle_changes,
+        errors,
+    )
+}
@@ -0,0 +1,481 @@
+// Copyright 2018-2022 contributors to the OpenLineage project
+// SPDX-License-Identifier: Apache-2.0
+
+// Tokenizes SQL text remembering where each token is, splits it into statements and parses
+// them one by one. Parser itself doesn't track positions, so this is what lets us point
+// errors to the place in the source.
+
+use sqlparser::ast::{Ident, ObjectName, Statement};
+use sqlparser::dialect::{Dialect, HiveDialect, RedshiftSqlDialect, SnowflakeDialect};
+use sqlparser::parser::{Parser, ParserError};
+use sqlparser::tokenizer::{Token, Tokenizer, Whitespace};
+
+use crate::{BigQueryDialect, ParseError};
+
+/// Position in SQL text. Line and column are 1-based, offset is in bytes.
+#[cfg_attr(feature = "python", pyo3::pyclass)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Location {
+    pub line: u64,
+    pub column: u64,
+    pub offset: usize,
+}
+
+impl Default for Location {
+    fn default() -> Self {
+        Location {
+            line: 1,
+            column: 1,
+            offset: 0,
+        }
+    }
+}
+
+impl Location {
+    fn advance(&mut self, text: &str) {
+        for c in text.chars() {
+            if c == '\n' {
+                self.line += 1;
+                self.column = 1;
+            } else {
+                self.column += 1;
+            }
+        }
+        self.offset += text.len();
+    }
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct LocatedToken {
+    pub token: Token,
+    pub start: Location,
+    pub end: Location,
+}
+
+impl LocatedToken {
+    pub fn is_whitespace(&self) -> bool {
+        matches!(self.token, Token::Whitespace(_))
+    }
+}
+
+pub(crate) fn tokenize(dialect: &dyn Dialect, sql: &str) -> Result<Vec<LocatedToken>, ParseError> {
+    let tokens = Tokenizer::new(dialect, sql)
+        .tokenize()
+        .map_err(|e| ParseError::Syntax {
+            message: format!("sql parser error: {}", e.message),
+            line: e.line,
+            column: e.col,
+        })?;
+
+    let mut location = Location::default();
+    Ok(tokens
+        .into_iter()
+        .map(|token| {
+            let start = location;
+            let rest = &sql[location.offset..];
+            location.advance(&rest[..token_len(&token, rest)]);
+            LocatedToken {
+                token,
+                start,
+                end: location,
+            }
+        })
+        .collect())
+}
+
+// Length in bytes of source text that token was read from, `rest` starting at the token.
+fn token_len(token: &Token, rest: &str) -> usize {
+    if let Token::Whitespace(Whitespace::Newline) = token {
+        if rest.starts_with("\r\n") {
+            return 2;
+        }
+    }
+    let text = token.to_string();
+    if rest.starts_with(&text) {
+        return text.len();
+    }
+    // Quoted tokens are displayed without escapes, so find where quoting ends instead.
+    if let Some(len) = quoted_len(rest) {
+        return len;
+    }
+    let mut len = text.len().min(rest.len());
+    while !rest.is_char_boundary(len) {
+        len -= 1;
+    }
+    len
+}
+
+// Length of quoted token, like 'it''s', "a""b", [name] or E'\n', at the start of `rest`.
+fn quoted_len(rest: &str) -> Option<usize> {
+    let mut chars = rest
+        .char_indices()
+        .skip_while(|(_, c)| c.is_ascii_alphabetic());
+    let (_, open) = chars.next()?;
+    let close = match open {
+        '\'' | '"' | '`' => open,
+        '[' => ']',
+        _ => return None,
+    };
+    let mut chars = chars.peekable();
+    while let Some((i, c)) = chars.next() {
+        if c == '\\' && close != ']' {
+            chars.next();
+        } else if c == close {
+            match chars.peek() {
+                Some((_, next)) if *next == close => {
+                    chars.next();
+                }
+                _ => return Some(i + c.len_utf8()),
+            }
+        }
+    }
+    None
+}
+
+// Splits tokens on semicolons, skipping statements that consist only of whitespace.
+pub(crate) fn split_statements(tokens: Vec<LocatedToken>) -> Vec<Vec<LocatedToken>> {
+    let mut statements = vec![];
+    let mut current = vec![];
+    for token in tokens {
+        if token.token == Token::SemiColon {
+            statements.push(std::mem::take(&mut current));
+        } else {
+            current.push(token);
+        }
+    }
+    statements.push(current);
+    statements.retain(|s: &Vec<LocatedToken>| s.iter().any(|t| !t.is_whitespace()));
+    statements
+}
+
+// Source text of the statement, without surrounding whitespace and comments.
+pub(crate) fn statement_text<'a>(sql: &'a str, tokens: &[LocatedToken]) -> &'a str {
+    let first = tokens.iter().find(|t| !t.is_whitespace());
+    let last = tokens.iter().rev().find(|t| !t.is_whitespace());
+    match (first, last) {
+        (Some(first), Some(last)) => &sql[first.start.offset..last.end.offset],
+        _ => "",
+    }
+}
+
+pub(crate) fn is_keyword(token: &Token, keyword: &str) -> bool {
+    match token {
+        Token::Word(word) => word.quote_style.is_none() && word.value.eq_ignore_ascii_case(keyword),
+        _ => false,
+    }
+}
+
+// Parts of dot-separated name, if tokens are exactly that.
+pub(crate) fn object_name(tokens: &[&Token]) -> Option<Vec<Ident>> {
+    if tokens.is_empty() {
+        return None;
+    }
+    tokens
+        .split(|token| **token == Token::Period)
+        .map(|part| match part {
+            [Token::Word(word)] => Some(Ident {
+                value: word.value.clone(),
+                quote_style: word.quote_style,
+            }),
+            _ => None,
+        })
+        .collect()
+}
+
+// Dot-separated name at the start of tokens, and tokens that follow it.
+pub(crate) fn split_name<'a, 'b>(tokens: &'a [&'b Token]) -> Option<(ObjectName, &'a [&'b Token])> {
+    let mut end = 1;
+    while end + 1 < tokens.len()
+        && *tokens[end] == Token::Period
+        && matches!(tokens[end + 1], Token::Word(_))
+    {
+        end += 2;
+    }
+    let name = object_name(tokens.get(..end)?)?;
+    Some((ObjectName(name), &tokens[end..]))
+}
+
+// Parts of the name written in text, like `db.schema."Orders"`, if text is just a name.
+pub(crate) fn parse_object_name(dialect: &dyn Dialect, text: &str) -> Option<Vec<Ident>> {
+    let tokens = Tokenizer::new(dialect, text).tokenize().ok()?;
+    let tokens: Vec<&Token> = tokens
+        .iter()
+        .filter(|t| !matches!(t, Token::Whitespace(_)))
+        .collect();
+    object_name(&tokens)
+}
+
+pub(crate) fn parse_statement(
+    dialect: &dyn Dialect,
+    tokens: &[LocatedToken],
+) -> Result<Statement, ParseError> {
+    let tokens_to_parse = rewrite_volatile(tokens);
+    let tokens_to_parse = rewrite_wildcard_tables(dialect, tokens_to_parse);
+    let tokens_to_parse = rewrite_view_modifiers(tokens_to_parse);
+    let tokens_to_parse = rewrite_merge_clauses(tokens_to_parse);
+    let tokens_to_parse = rewrite_qualify(dialect, tokens_to_parse);
+    let mut parser = Parser::new(tokens_to_parse, dialect);
+    let result = parser.parse_statement().and_then(|stmt| {
+        if parser.peek_token() == Token::EOF {
+            Ok(stmt)
+        } else {
+            Err(ParserError::ParserError(format!(
+                "Expected end of statement, found: {}",
+                parser.peek_token()
+            )))
+        }
+    });
+    result.map_err(|e| {
+        let message = e.to_string();
+        let location = error_location(&mut parser, tokens, &message);
+        ParseError::Syntax {
+            message,
+            line: location.line,
+            column: location.column,
+        }
+    })
+}
+
+// Parser doesn't know `CREATE VOLATILE TABLE` used by Teradata and Snowflake. Volatile table
+// lives only until the end of the session, just like temporary one, so we parse it as such.
+fn rewrite_volatile(tokens: &[LocatedToken]) -> Vec<Token> {
+    let mut result: Vec<Token> = tokens.iter().map(|t| t.token.clone()).collect();
+    let significant: Vec<usize> = (0..result.len())
+        .filter(|i| !tokens[*i].is_whitespace())
+        .collect();
+    for pair in significant.windows(2) {
+        if let (Token::Word(word), Token::Word(next)) = (&result[pair[0]], &result[pair[1]]) {
+            if word.value.eq_ignore_ascii_case("VOLATILE")
+                && word.quote_style.is_none()
+                && next.value.eq_ignore_ascii_case("TABLE")
+            {
+                result[pair[0]] = Token::make_keyword("TEMPORARY");
+            }
+        }
+    }
+    result
+}
+
+// BigQuery wildcard table, like `dataset.events_*`, can be written without quotes, and then
+// it's tokenized as name followed by `*`. Parser expects one identifier, so they are joined.
+fn rewrite_wildcard_tables(dialect: &dyn Dialect, mut tokens: Vec<Token>) -> Vec<Token> {
+    if !dialect.is::<BigQueryDialect>() {
+        return tokens;
+    }
+    let significant: Vec<usize> = (0..tokens.len())
+        .filter(|i| !matches!(tokens[*i], Token::Whitespace(_)))
+        .collect();
+    let is_word = |token: &Token| matches!(token, Token::Word(_));
+    for k in 0..significant.len() {
+        let keyword = &tokens[significant[k]];
+        if !(is_keyword(keyword, "FROM") || is_keyword(keyword, "JOIN")) {
+            continue;
+        }
+        // Last part of dot-separated name that follows.
+        let mut last = match significant.get(k + 1) {
+            Some(i) if is_word(&tokens[*i]) => *i,
+            _ => continue,
+        };
+        let mut n = k + 1;
+        while let (Some(period), Some(word)) = (significant.get(n + 1), significant.get(n + 2)) {
+            if tokens[*period] != Token::Period || !is_word(&tokens[*word]) {
+                break;
+            }
+            last = *word;
+            n += 2;
+        }
+        if let (Token::Word(word), Some(Token::Mul)) = (&tokens[last], tokens.get(last + 1)) {
+            tokens[last] = Token::make_word(&format!("{}*", word.value), word.quote_style);
+            tokens[last + 1] = Token::Whitespace(Whitespace::Space);
+        }
+    }
+    tokens
+}
+
+// Parser doesn't know some modifiers of CREATE VIEW: `SECURE`, `IF NOT EXISTS` and
+// `COPY GRANTS` of Snowflake, `OPTIONS (...)` of BigQuery and `WITH NO SCHEMA BINDING`
+// of Redshift late-binding views. They don't change what view reads, so they are blanked out.
+fn rewrite_view_modifiers(mut tokens: Vec<Token>) -> Vec<Token> {
+    let significant: Vec<usize> = (0..tokens.len())
+        .filter(|i| !matches!(tokens[*i], Token::Whitespace(_)))
+        .collect();
+    let keyword_at = |i: usize, keyword: &str| match significant.get(i) {
+        Some(i) => is_keyword(&tokens[*i], keyword),
+        None => false,
+    };
+    // Modifiers are between CREATE and AS that starts the query.
+    let query_start = (0..significant.len()).find(|i| keyword_at(*i, "AS"));
+    let view = (1..query_start.unwrap_or(0)).find(|i| keyword_at(*i, "VIEW"));
+    let (query_start, view) = match (query_start, view) {
+        (Some(query_start), Some(view)) if keyword_at(0, "CREATE") => (query_start, view),
+        _ => return tokens,
+    };
+    let mut blank: Vec<usize> = vec![];
+    for i in 1..query_start {
+        if i < view && keyword_at(i, "SECURE") {
+            blank.push(i);
+        } else if i == view + 1
+            && keyword_at(i, "IF")
+            && keyword_at(i + 1, "NOT")
+            && keyword_at(i + 2, "EXISTS")
+        {
+            blank.extend(i..i + 3);
+        } else if keyword_at(i, "COPY") && keyword_at(i + 1, "GRANTS") {
+            blank.extend(i..i + 2);
+        } else if keyword_at(i, "OPTIONS")
+            && significant.get(i + 1).map(|i| &tokens[*i]) == Some(&Token::LParen)
+        {
+            let mut depth = 0;
+            for j in i + 1..query_start {
+                match tokens[significant[j]] {
+                    Token::LParen => depth += 1,
+                    Token::RParen => depth -= 1,
+                    _ => {}
+                }
+                if depth == 0 {
+                    blank.extend(i..=j);
+                    break;
+                }
+            }
+        }
+    }
+    let len = significant.len();
+    if len >= 4
+        && ["WITH", "NO", "SCHEMA", "BINDING"]
+            .iter()
+            .enumerate()
+            .all(|(k, keyword)| keyword_at(len - 4 + k, keyword))
+    {
+        blank.extend(len - 4..len);
+    }
+    for i in blank {
+        tokens[significant[i]] = Token::Whitespace(Whitespace::Space);
+    }
+    tokens
+}
+
+// Parser knows only `WHEN MATCHED` and `WHEN NOT MATCHED` clauses of MERGE. T-SQL's and
+// Databricks' `WHEN NOT MATCHED BY SOURCE` updates or deletes target rows, just like
+// `WHEN MATCHED` does, and `WHEN NOT MATCHED BY TARGET` is plain `WHEN NOT MATCHED`.
+fn rewrite_merge_clauses(mut tokens: Vec<Token>) -> Vec<Token> {
+    let significant: Vec<usize> = (0..tokens.len())
+        .filter(|i| !matches!(tokens[*i], Token::Whitespace(_)))
+        .collect();
+    let keyword_at = |i: usize, keyword: &str| match significant.get(i) {
+        Some(i) => is_keyword(&tokens[*i], keyword),
+        None => false,
+    };
+    if !keyword_at(0, "MERGE") {
+        return tokens;
+    }
+    let mut blank: Vec<usize> = vec![];
+    for i in 1..significant.len() {
+        if !(keyword_at(i, "WHEN")
+            && keyword_at(i + 1, "NOT")
+            && keyword_at(i + 2, "MATCHED")
+            && keyword_at(i + 3, "BY"))
+        {
+            continue;
+        }
+        if keyword_at(i + 4, "SOURCE") {
+            blank.extend([i + 1, i + 3, i + 4]);
+        } else if keyword_at(i + 4, "TARGET") {
+            blank.extend([i + 3, i + 4]);
+        }
+    }
+    for i in blank {
+        tokens[significant[i]] = Token::Whitespace(Whitespace::Space);
+    }
+    tokens
+}
+
+// Parser doesn't know QUALIFY, which filters rows by results of window functions in
+// Snowflake, BigQuery, Redshift and Databricks, read with Hive dialect. It follows HAVING, so it's parsed as HAVING, or joined
+// to one with AND. Neither changes what the query reads. Elsewhere `qualify` is just a name,
+// so only the word in place of a clause of SELECT, after its FROM, is rewritten.
+fn rewrite_qualify(dialect: &dyn Dialect, mut tokens: Vec<Token>) -> Vec<Token> {
+    if !(dialect.is::<SnowflakeDialect>()
+        || dialect.is::<BigQueryDialect>()
+        || dialect.is::<RedshiftSqlDialect>()
+        || dialect.is::<HiveDialect>())
+    {
+        return tokens;
+    }
+    let significant: Vec<usize> = (0..tokens.len())
+        .filter(|i| !matches!(tokens[*i], Token::Whitespace(_)))
+        .collect();
+    // Clause comes after table or expression, which doesn't end with keyword or operator.
+    let ends_operand = |token: &Token| match token {
+        Token::Word(_) => !CONTINUING_KEYWORDS.iter().any(|k| is_keyword(token, k)),
+        Token::Number(..) | Token::SingleQuotedString(_) | Token::RParen | Token::Mul => true,
+        _ => false,
+    };
+    // And is followed by condition.
+    let starts_condition =
+        |token: &Token| matches!(token, Token::Word(_) | Token::Number(..) | Token::LParen);
+
+    // Whether SELECT at each level of parentheses has its FROM and HAVING already.
+    let mut levels = vec![(false, false)];
+    let mut rewrites: Vec<(usize, &str)> = vec![];
+    for (k, &i) in significant.iter().enumerate() {
+        let token = &tokens[i];
+        match token {
+            Token::LParen => levels.push((false, false)),
+            Token::RParen if levels.len() > 1 => {
+                levels.pop();
+            }
+            _ => {
+                let (from, having) = levels.last_mut().expect("there is always the outer level");
+                if is_keyword(token, "SELECT") {
+                    *from = false;
+                    *having = false;
+                } else if is_keyword(token, "FROM") {
+                    *from = true;
+                } else if is_keyword(token, "HAVING") {
+                    *having = true;
+                } else if is_keyword(token, "QUALIFY")
+                    && *from
+                    && k > 0
+                    && ends_operand(&tokens[significant[k - 1]])
+                    && matches!(significant.get(k + 1), Some(next) if starts_condition(&tokens[*next]))
+                {
+                    rewrites.push((i, if *having { "AND" } else { "HAVING" }));
+                    *having = true;
+                }
+            }
+        }
+    }
+    for (i, keyword) in rewrites {
+        tokens[i] = Token::make_keyword(keyword);
+    }
+    tokens
+}
+
+// Keywords after which expression or table name goes on.
+const CONTINUING_KEYWORDS: &[&str] = &[
+    "SELECT", "DISTINCT", "FROM", "JOIN", "AS", "ON", "WHERE", "BY", "HAVING", "AND", "OR", "NOT",
+    "IN", "IS", "LIKE", "CASE", "WHEN", "THEN", "ELSE",
+];
+
+// Parser doesn't report where it failed, but it stops right after the offending token,
+// or at it, so we find it by counting tokens left.
+fn error_location(parser: &mut Parser, tokens: &[LocatedToken], message: &str) -> Location {
+    let mut remaining = 0;
+    while parser.next_token() != Token::EOF {
+        remaining += 1;
+    }
+    let significant: Vec<&LocatedToken> = tokens.iter().filter(|t| !t.is_whitespace()).collect();
+    let mut index = significant.len().saturating_sub(remaining);
+
+    if let Some((_, found)) = message.rsplit_once("found: ") {
+        let matches =
+            |i: usize| significant.get(i).map(|t| t.token.to_string()) == Some(found.to_string());
+        if !matches(index) && index > 0 && matches(index - 1) {
+            index -= 1;
+        }
+    }
+    match significant.get(index) {
+        Some(token) => token.start,
+        None => tokens.last().map(|t| t.end).unwrap_or_default(),
+    }
+}
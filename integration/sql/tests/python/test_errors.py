@@ -0,0 +1,57 @@
+# Copyright 2018-2022 contributors to the OpenLineage project
+# SPDX-License-Identifier: Apache-2.0
+
+import pytest
+
+from openlineage_sql import (
+    DbTableMeta,
+    EmptySqlError,
+    SqlParseError,
+    SqlSyntaxError,
+    UnsupportedSqlError,
+    parse,
+)
+
+
+def test_errors_are_runtime_errors():
+    assert issubclass(SqlParseError, RuntimeError)
+    for error in (SqlSyntaxError, UnsupportedSqlError, EmptySqlError):
+        assert issubclass(error, SqlParseError)
+
+
+def test_syntax_error():
+    with pytest.raises(SqlSyntaxError) as info:
+        parse(["SELEC * FROM a"])
+    assert "Expected an SQL statement, found: SELEC" in str(info.value)
+
+
+def test_unsupported_error():
+    with pytest.raises(UnsupportedSqlError) as info:
+        parse(["SELECT * FROM a.b.c.d"], dialect="postgres")
+    assert str(info.value) == "Table name a.b.c.d has more than three parts"
+
+
+def test_empty_error():
+    with pytest.raises(EmptySqlError) as info:
+        parse([" ; "])
+    assert str(info.value) == "Empty statement list"
+
+
+def test_errors_caught_as_runtime_error():
+    with pytest.raises(RuntimeError):
+        parse(["SELEC * FROM a"])
+
+
+def test_tolerant_returns_partial_lineage():
+    sql = ["INSERT INTO b SELECT * FROM a", "SELEC x", "INSERT INTO d SELECT * FROM c"]
+    with pytest.raises(SqlSyntaxError):
+        parse(sql)
+
+    metadata = parse(sql, tolerant=True)
+    assert metadata.in_tables == [DbTableMeta("a"), DbTableMeta("c")]
+    assert metadata.out_tables == [DbTableMeta("b"), DbTableMeta("d")]
+    assert len(metadata.errors) == 1
+    error = metadata.errors[0]
+    assert error.index == 1
+    assert error.statement == "SELEC x"
+    assert "Expected an SQL statement, found: SELEC" in error.reason
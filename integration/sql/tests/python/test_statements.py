@@ -0,0 +1,41 @@
+# Copyright 2018-2022 contributors to the OpenLineage project
+# SPDX-License-Identifier: Apache-2.0
+
+from openlineage_sql import DbTableMeta, parse_statements
+
+
+def test_statements_in_order():
+    statements = parse_statements(
+        [
+            "INSERT INTO b SELECT * FROM a; INSERT INTO c SELECT * FROM b",
+            "SELECT * FROM d",
+        ]
+    )
+    assert [statement.index for statement in statements] == [0, 1, 2]
+    assert [statement.statement for statement in statements] == [
+        "INSERT INTO b SELECT * FROM a",
+        "INSERT INTO c SELECT * FROM b",
+        "SELECT * FROM d",
+    ]
+    assert [statement.in_tables for statement in statements] == [
+        [DbTableMeta("a")],
+        [DbTableMeta("b")],
+        [DbTableMeta("d")],
+    ]
+    assert [statement.out_tables for statement in statements] == [
+        [DbTableMeta("b")],
+        [DbTableMeta("c")],
+        [],
+    ]
+
+
+def test_statements_tolerant():
+    statements = parse_statements(
+        ["INSERT INTO b SELECT * FROM a", "SELEC x", "SELECT * FROM c"], tolerant=True
+    )
+    assert [statement.index for statement in statements] == [0, 1, 2]
+    assert statements[0].errors == []
+    assert statements[0].out_tables == [DbTableMeta("b")]
+    assert statements[1].in_tables == []
+    assert [error.index for error in statements[1].errors] == [1]
+    assert statements[2].in_tables == [DbTableMeta("c")]
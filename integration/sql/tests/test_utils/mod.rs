@@ -1,5 +1,7 @@
 use openlineage_sql::{
-    get_dialect, get_generic_dialect, parse_multiple_statements, parse_sql, DbTableMeta, SqlMeta,
+    get_dialect, get_generic_dialect, parse_multiple_statements,
+    parse_multiple_statements_with_options, parse_sql, ColumnLineage, ColumnMeta, DatasetOperation,
+    DbTableMeta, Operation, ParseOptions, SqlMeta,
 };
 use sqlparser::dialect::PostgreSqlDialect;
 
@@ -32,12 +34,51 @@ pub fn test_sql_dialect(sql: &str, dialect: &str) -> SqlMeta {
 }
 
 pub fn table(name: &str) -> Vec<DbTableMeta> {
-    vec![DbTableMeta::py_new(String::from(name))]
+    vec![DbTableMeta::new_default_dialect(String::from(name))]
 }
 
 pub fn tables(names: Vec<&str>) -> Vec<DbTableMeta> {
     names
         .into_iter()
-        .map(|name| DbTableMeta::py_new(String::from(name)))
+        .map(|name| DbTableMeta::new_default_dialect(String::from(name)))
         .collect()
 }
+
+// Tables read and written by SQL, for tests that check only them. SqlMeta is equal to it
+// when it has the same in_tables and out_tables, whatever else it reports.
+#[derive(Debug)]
+pub struct TableLineage {
+    pub in_tables: Vec<DbTableMeta>,
+    pub out_tables: Vec<DbTableMeta>,
+}
+
+impl PartialEq<TableLineage> for SqlMeta {
+    fn eq(&self, other: &TableLineage) -> bool {
+        self.in_tables == other.in_tables && self.out_tables == other.out_tables
+    }
+}
+
+pub fn column(table: &str, name: &str) -> ColumnMeta {
+    ColumnMeta::new(
+        String::from(name),
+        Some(DbTableMeta::new_default_dialect(String::from(table))),
+    )
+}
+
+pub fn lineage(descendant: ColumnMeta, lineage: Vec<ColumnMeta>) -> ColumnLineage {
+    ColumnLineage {
+        descendant,
+        lineage,
+    }
+}
+
+pub fn operation(table: &str, operation: Operation) -> DatasetOperation {
+    DatasetOperation::new(
+        DbTableMeta::new_default_dialect(String::from(table)),
+        operation,
+    )
+}
+
+pub fn test_sql_options(sql: &str, dialect: &str, options: &ParseOptions) -> SqlMeta {
+    parse_multiple_statements_with_options(vec![sql], get_dialect(dialect), options).unwrap()
+}
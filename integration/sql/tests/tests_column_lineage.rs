@@ -0,0 +1,239 @@
+// Copyright 2018-2022 contributors to the OpenLineage project
+// SPDX-License-Identifier: Apache-2.0
+
+use openlineage_sql::{ColumnMeta, DbTableMeta};
+
+#[macro_use]
+mod test_utils;
+use test_utils::*;
+
+#[test]
+fn column_lineage_insert_select() {
+    assert_eq!(
+        test_sql("INSERT INTO tgt SELECT a, b + c AS d FROM src").column_lineage,
+        vec![
+            lineage(column("tgt", "a"), vec![column("src", "a")]),
+            lineage(
+                column("tgt", "d"),
+                vec![column("src", "b"), column("src", "c")]
+            ),
+        ]
+    )
+}
+
+#[test]
+fn column_lineage_insert_target_columns() {
+    assert_eq!(
+        test_sql("INSERT INTO tgt (x, y) SELECT s.a, upper(s.b) FROM src s").column_lineage,
+        vec![
+            lineage(column("tgt", "x"), vec![column("src", "a")]),
+            lineage(column("tgt", "y"), vec![column("src", "b")]),
+        ]
+    )
+}
+
+#[test]
+fn column_lineage_join_aliases() {
+    assert_eq!(
+        test_sql(
+            "
+            INSERT INTO tgt
+            SELECT o.id, c.name AS customer, o.amount * c.discount AS total
+            FROM sales.orders o
+            JOIN sales.customers c ON o.customer_id = c.id"
+        )
+        .column_lineage,
+        vec![
+            lineage(column("tgt", "customer"), vec![column("sales.customers", "name")]),
+            lineage(column("tgt", "id"), vec![column("sales.orders", "id")]),
+            lineage(
+                column("tgt", "total"),
+                vec![
+                    column("sales.customers", "discount"),
+                    column("sales.orders", "amount")
+                ]
+            ),
+        ]
+    )
+}
+
+#[test]
+fn column_lineage_cte_and_subquery() {
+    assert_eq!(
+        test_sql(
+            "
+            WITH totals (user_id, total) AS (
+                SELECT user_id, SUM(amount) FROM transactions GROUP BY user_id
+            )
+            INSERT INTO report
+            SELECT t.user_id, sub.name, t.total
+            FROM totals t
+            JOIN (SELECT id, first_name || last_name AS name FROM users) sub
+            ON t.user_id = sub.id"
+        )
+        .column_lineage,
+        vec![
+            lineage(
+                column("report", "name"),
+                vec![column("users", "first_name"), column("users", "last_name")]
+            ),
+            lineage(column("report", "total"), vec![column("transactions", "amount")]),
+            lineage(
+                column("report", "user_id"),
+                vec![column("transactions", "user_id")]
+            ),
+        ]
+    )
+}
+
+#[test]
+fn column_lineage_union() {
+    assert_eq!(
+        test_sql(
+            "
+            CREATE TABLE all_events AS
+            SELECT id, ts FROM clicks
+            UNION ALL
+            SELECT event_id, created_at FROM views"
+        )
+        .column_lineage,
+        vec![
+            lineage(
+                column("all_events", "id"),
+                vec![column("clicks", "id"), column("views", "event_id")]
+            ),
+            lineage(
+                column("all_events", "ts"),
+                vec![column("clicks", "ts"), column("views", "created_at")]
+            ),
+        ]
+    )
+}
+
+#[test]
+fn column_lineage_select_into() {
+    assert_eq!(
+        test_sql("SELECT a AS b INTO tgt FROM src").column_lineage,
+        vec![lineage(column("tgt", "b"), vec![column("src", "a")])]
+    )
+}
+
+#[test]
+fn column_lineage_merge() {
+    assert_eq!(
+        test_sql(
+            "
+            MERGE INTO tgt t
+            USING (SELECT id, val FROM src) s
+            ON t.id = s.id
+            WHEN MATCHED THEN UPDATE SET t.val = s.val
+            WHEN NOT MATCHED THEN INSERT (id, val) VALUES (s.id, s.val)"
+        )
+        .column_lineage,
+        vec![
+            lineage(column("tgt", "id"), vec![column("src", "id")]),
+            lineage(column("tgt", "val"), vec![column("src", "val")]),
+        ]
+    )
+}
+
+#[test]
+fn column_lineage_ambiguous_column() {
+    assert_eq!(
+        test_sql("INSERT INTO tgt SELECT a FROM src1, src2").column_lineage,
+        vec![lineage(
+            column("tgt", "a"),
+            vec![ColumnMeta::new(String::from("a"), None)]
+        )]
+    )
+}
+
+#[test]
+fn column_lineage_plain_select() {
+    assert_eq!(test_sql("SELECT a, b FROM src").column_lineage, vec![])
+}
+
+#[test]
+fn column_lineage_facet_json() {
+    let meta = test_sql("INSERT INTO mart.tgt SELECT s.a, s.b + c.b AS b FROM src s, other c");
+    let facets = meta.column_lineage_facets("postgres://localhost:5432", "producer");
+    assert_eq!(facets.len(), 1);
+    assert_eq!(facets[0].0, DbTableMeta::new_default_dialect(String::from("mart.tgt")));
+
+    let json: serde_json::Value = serde_json::from_str(&facets[0].1.to_json()).unwrap();
+    assert_eq!(
+        json,
+        serde_json::json!({
+            "_producer": "producer",
+            "_schemaURL": "https://openlineage.io/spec/facets/1-0-1/ColumnLineageDatasetFacet.json#/$defs/ColumnLineageDatasetFacet",
+            "fields": {
+                "a": {
+                    "inputFields": [
+                        {"namespace": "postgres://localhost:5432", "name": "src", "field": "a"}
+                    ]
+                },
+                "b": {
+                    "inputFields": [
+                        {"namespace": "postgres://localhost:5432", "name": "other", "field": "b"},
+                        {"namespace": "postgres://localhost:5432", "name": "src", "field": "b"}
+                    ]
+                }
+            }
+        })
+    )
+}
+
+#[test]
+fn column_lineage_recursive_cte() {
+    assert_eq!(
+        test_sql(
+            "
+            WITH RECURSIVE tree (id, depth) AS (
+                SELECT id, 0 FROM nodes WHERE parent_id IS NULL
+                UNION ALL
+                SELECT n.id, t.depth + 1 FROM nodes n JOIN tree t ON n.parent_id = t.id
+            )
+            INSERT INTO flat_tree SELECT id, depth FROM tree"
+        )
+        .column_lineage,
+        vec![
+            lineage(column("flat_tree", "depth"), vec![]),
+            lineage(column("flat_tree", "id"), vec![column("nodes", "id")]),
+        ]
+    )
+}
+
+#[test]
+fn column_lineage_lateral_subquery() {
+    assert_eq!(
+        test_sql(
+            "
+            INSERT INTO tgt
+            SELECT o.id, l.total
+            FROM orders o,
+            LATERAL (SELECT SUM(i.amount) AS total FROM items i WHERE i.order_id = o.id) l"
+        )
+        .column_lineage,
+        vec![
+            lineage(column("tgt", "id"), vec![column("orders", "id")]),
+            lineage(column("tgt", "total"), vec![column("items", "amount")]),
+        ]
+    )
+}
+
+#[test]
+fn column_lineage_correlated_subquery() {
+    assert_eq!(
+        test_sql(
+            "
+            INSERT INTO tgt
+            SELECT (SELECT MAX(p.price) FROM prices p WHERE p.product_id = o.product_id) AS price
+            FROM orders o"
+        )
+        .column_lineage,
+        vec![lineage(
+            column("tgt", "price"),
+            vec![column("prices", "price")]
+        )]
+    )
+}
@@ -3,7 +3,7 @@
 
 extern crate core;
 
-use openlineage_sql::{parse_sql, SqlMeta};
+use openlineage_sql::{parse_sql, ParseError, SqlMeta};
 use sqlparser::dialect::SnowflakeDialect;
 use std::sync::Arc;
 
@@ -49,6 +49,10 @@ fn parse_copy_from() {
             None
         )
         .unwrap_err(),
-        "sql parser error: Expected FROM or TO, found: SCHEMA"
+        ParseError::Syntax {
+            message: String::from("sql parser error: Expected FROM or TO, found: SCHEMA"),
+            line: 2,
+            column: 23
+        }
     )
 }
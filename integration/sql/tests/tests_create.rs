@@ -2,7 +2,6 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use sqlparser::dialect::HiveDialect;
-use openlineage_sql::SqlMeta;
 
 #[macro_use]
 mod test_utils;
@@ -20,9 +19,9 @@ fn test_create_table() {
         Address varchar(255),
         City varchar(255));"
         ),
-        SqlMeta {
+        TableLineage {
             in_tables: vec![],
-            out_tables: table("Persons")
+            out_tables: table("persons")
         }
     )
 }
@@ -31,7 +30,7 @@ fn test_create_table() {
 fn test_create_table_like() {
     assert_eq!(
         test_sql("CREATE TABLE new LIKE original"),
-        SqlMeta {
+        TableLineage {
             in_tables: table("original"),
             out_tables: table("new")
         }
@@ -42,7 +41,7 @@ fn test_create_table_like() {
 fn test_create_table_clone() {
     assert_eq!(
         test_sql("CREATE OR REPLACE TABLE new CLONE original"),
-        SqlMeta {
+        TableLineage {
             in_tables: table("original"),
             out_tables: table("new")
         }
@@ -59,9 +58,9 @@ fn test_create_and_insert() {
         value varchar(255));
         INSERT INTO Persons SELECT key, value FROM temp.table;"
         ),
-        SqlMeta {
+        TableLineage {
             in_tables: tables(vec!["temp.table"]),
-            out_tables: table("Persons")
+            out_tables: table("persons")
         }
     )
 }
@@ -73,9 +72,9 @@ fn create_and_insert_multiple_stmts() {
             "CREATE TABLE Persons (key int, value varchar(255));",
             "INSERT INTO Persons SELECT key, value FROM temp.table;"
         ]),
-        SqlMeta {
+        TableLineage {
             in_tables: tables(vec!["temp.table"]),
-            out_tables: table("Persons")
+            out_tables: table("persons")
         }
     )
 }
@@ -101,9 +100,9 @@ fn create_hive_external_table_if_not_exist() {
                     LOCATION 's3://abc.ingest/sqlserver/Testing/Versions/ds=2022-08-10'
                     TBLPROPERTIES ('parquet.compression'='SNAPPY');
         ", "hive"
-        ), SqlMeta {
+        ), TableLineage {
             in_tables: vec![],
-            out_tables: table("Testing_Versions_latest")
+            out_tables: table("testing_versions_latest")
         }
     )
 }
@@ -117,9 +116,9 @@ fn create_replace_as_select() {
             FROM dwh_dev.commons.calendar
             WHERE date BETWEEN '2022-01-01' AND CURRENT_DATE
         )"),
-        SqlMeta {
+        TableLineage {
             in_tables: table("dwh_dev.commons.calendar"),
-            out_tables: table("DATA_TEAM_DEMOS.ALL_DAYS")
+            out_tables: table("data_team_demos.all_days")
         }
     )
 }
\ No newline at end of file
@@ -3,7 +3,7 @@
 
 extern crate core;
 
-use openlineage_sql::{parse_sql, SqlMeta};
+use openlineage_sql::{parse_sql, ParseError};
 use sqlparser::dialect::PostgreSqlDialect;
 use std::sync::Arc;
 
@@ -28,7 +28,7 @@ fn parse_simple_cte() {
                     WHERE count > 1000 OR balance > 100000;
                 ",
         ),
-        SqlMeta {
+        TableLineage {
             in_tables: table("transactions"),
             out_tables: table("potential_fraud")
         }
@@ -54,7 +54,11 @@ fn parse_bugged_cte() {
             None
         )
         .unwrap_err(),
-        "sql parser error: Expected ), found: user_id"
+        ParseError::Syntax {
+            message: String::from("sql parser error: Expected ), found: user_id"),
+            line: 3,
+            column: 28
+        }
     )
 }
 
@@ -78,7 +82,7 @@ fn parse_recursive_cte() {
             SELECT employee_id, manager_id, full_name FROM subordinates;
         "
         ),
-        SqlMeta {
+        TableLineage {
             in_tables: table("employees"),
             out_tables: table("sub_employees")
         }
@@ -102,10 +106,10 @@ fn multiple_ctes() {
             ON c.id = o.customer_id
         "
         ),
-        SqlMeta {
+        TableLineage {
             in_tables: tables(vec![
-                "DEMO_DB.public.stg_customers",
-                "DEMO_DB.public.stg_orders"
+                "demo_db.public.stg_customers",
+                "demo_db.public.stg_orders"
             ]),
             out_tables: vec![]
         }
@@ -185,9 +189,43 @@ fn cte_insert_overwrite() {
               grps.grp
         "
         ),
-        SqlMeta {
+        TableLineage {
             in_tables: table("d_n.f_p_s"),
             out_tables: table("dev_d_n.f_p_s_m")
         }
     )
 }
+
+#[test]
+fn parse_cte_shadowing_table() {
+    assert_eq!(
+        test_sql(
+            "
+            WITH orders AS (SELECT * FROM orders WHERE status = 'done')
+            INSERT INTO report SELECT * FROM orders",
+        ),
+        TableLineage {
+            in_tables: table("orders"),
+            out_tables: table("report")
+        }
+    );
+}
+
+#[test]
+fn parse_cte_scoped_to_subquery() {
+    assert_eq!(
+        test_sql(
+            "
+            SELECT *
+            FROM (
+                WITH orders AS (SELECT * FROM raw_orders)
+                SELECT * FROM orders
+            ) o
+            JOIN orders ON o.id = orders.id",
+        ),
+        TableLineage {
+            in_tables: tables(vec!["orders", "raw_orders"]),
+            out_tables: vec![]
+        }
+    );
+}
@@ -4,14 +4,13 @@
 #[macro_use]
 mod test_utils;
 
-use openlineage_sql::SqlMeta;
 use test_utils::*;
 
 #[test]
 fn delete_from() {
     assert_eq!(
         test_sql("DELETE FROM a.b WHERE x = 0",),
-        SqlMeta {
+        TableLineage {
             in_tables: vec![],
             out_tables: table("a.b")
         }
@@ -30,7 +29,7 @@ fn delete_from_using() {
                 ) AS duplicates
                 WHERE a.b.col = duplicates.col",
         ),
-        SqlMeta {
+        TableLineage {
             in_tables: table("b.c"),
             out_tables: table("a.b")
         }
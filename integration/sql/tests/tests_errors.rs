@@ -0,0 +1,172 @@
+// Copyright 2018-2022 contributors to the OpenLineage project
+// SPDX-License-Identifier: Apache-2.0
+
+use openlineage_sql::{
+    get_dialect, parse_multiple_statements, parse_multiple_statements_with_options, parse_sql,
+    ParseError, ParseOptions, SqlMeta,
+};
+
+#[macro_use]
+mod test_utils;
+use test_utils::*;
+
+#[test]
+fn error_syntax_position_after_quoted_string() {
+    assert_eq!(
+        parse_sql(
+            "INSERT INTO tgt\nSELECT 'it''s', \"a b\" c d FROM src",
+            get_dialect("postgres"),
+            None
+        )
+        .unwrap_err(),
+        ParseError::Syntax {
+            message: String::from("sql parser error: Expected end of statement, found: d"),
+            line: 2,
+            column: 25
+        }
+    )
+}
+
+#[test]
+fn error_syntax_in_later_statement() {
+    assert_eq!(
+        parse_sql(
+            "SELECT * FROM a;\nSELECT * FROM",
+            get_dialect("postgres"),
+            None
+        )
+        .unwrap_err(),
+        ParseError::Syntax {
+            message: String::from("sql parser error: Expected identifier, found: EOF"),
+            line: 2,
+            column: 14
+        }
+    )
+}
+
+#[test]
+fn error_tokenizer() {
+    assert!(matches!(
+        parse_sql("SELECT 'abc FROM t", get_dialect("postgres"), None).unwrap_err(),
+        ParseError::Syntax { line: 1, .. }
+    ))
+}
+
+#[test]
+fn error_unsupported_table_factor() {
+    match parse_sql(
+        "SELECT * FROM (a JOIN b ON a.id = b.id)",
+        get_dialect("postgres"),
+        None,
+    )
+    .unwrap_err()
+    {
+        ParseError::Unsupported { node, .. } => assert_eq!(node, "TableFactor::NestedJoin"),
+        err => panic!("unexpected error: {}", err),
+    }
+}
+
+#[test]
+fn error_table_name_with_too_many_parts() {
+    assert_eq!(
+        parse_sql(
+            "SELECT * FROM a.b.c.d",
+            get_dialect("postgres"),
+            None,
+        )
+        .unwrap_err(),
+        ParseError::Unsupported {
+            node: String::from("ObjectName"),
+            message: String::from("Table name a.b.c.d has more than three parts"),
+        }
+    );
+}
+
+#[test]
+fn error_table_name_with_server_in_generic_dialect() {
+    assert_eq!(
+        parse_sql("SELECT * FROM a.b.c.d", get_dialect("generic"), None).unwrap_err(),
+        ParseError::Unsupported {
+            node: String::from("ObjectName"),
+            message: String::from("Table name a.b.c.d has more than three parts"),
+        }
+    );
+}
+
+#[test]
+fn error_empty_input() {
+    assert_eq!(
+        parse_multiple_statements(vec!["SELECT 1", " ; "], get_dialect("postgres"), None)
+            .unwrap_err(),
+        ParseError::EmptyInput
+    )
+}
+
+fn test_sql_tolerant(sql: Vec<&str>) -> SqlMeta {
+    let options = ParseOptions {
+        tolerant: true,
+        ..ParseOptions::default()
+    };
+    parse_multiple_statements_with_options(sql, get_dialect("postgres"), &options).unwrap()
+}
+
+#[test]
+fn tolerant_skip_unparseable_statement() {
+    let meta = test_sql_tolerant(vec![
+        "SELECT * FROM a; SELEC oops FROM x;\nINSERT INTO b SELECT * FROM c",
+        "SELECT * FROM d",
+    ]);
+    assert_eq!(
+        meta,
+        TableLineage {
+            in_tables: tables(vec!["a", "c", "d"]),
+            out_tables: table("b")
+        }
+    );
+    assert_eq!(meta.errors.len(), 1);
+    assert_eq!(meta.errors[0].index, 1);
+    assert_eq!(meta.errors[0].statement, "SELEC oops FROM x");
+    assert!(matches!(
+        meta.errors[0].reason,
+        ParseError::Syntax {
+            line: 1,
+            column: 18,
+            ..
+        }
+    ));
+}
+
+#[test]
+fn tolerant_skip_unsupported_table_factor() {
+    let meta = test_sql_tolerant(vec![
+        "INSERT INTO t SELECT * FROM (a JOIN b ON a.id = b.id), c",
+        "SELECT 'unterminated",
+        "SELECT * FROM d",
+    ]);
+    assert_eq!(
+        meta,
+        TableLineage {
+            in_tables: tables(vec!["c", "d"]),
+            out_tables: table("t")
+        }
+    );
+    assert_eq!(
+        meta.errors
+            .iter()
+            .map(|e| (e.index, e.statement.as_str()))
+            .collect::<Vec<_>>(),
+        vec![
+            (0, "INSERT INTO t SELECT * FROM (a JOIN b ON a.id = b.id), c"),
+            (1, "SELECT 'unterminated")
+        ]
+    );
+    assert!(matches!(
+        &meta.errors[0].reason,
+        ParseError::Unsupported { node, .. } if node == "TableFactor::NestedJoin"
+    ));
+}
+
+#[test]
+fn strict_mode_has_no_errors() {
+    assert_eq!(test_sql("SELECT * FROM a").errors, vec![]);
+}
@@ -1,7 +1,6 @@
 // Copyright 2018-2022 contributors to the OpenLineage project
 // SPDX-License-Identifier: Apache-2.0
 
-use openlineage_sql::SqlMeta;
 
 #[macro_use]
 mod test_utils;
@@ -11,9 +10,9 @@ use test_utils::*;
 fn insert_values() {
     assert_eq!(
         test_sql("INSERT INTO TEST VALUES(1)",),
-        SqlMeta {
+        TableLineage {
             in_tables: vec![],
-            out_tables: table("TEST")
+            out_tables: table("test")
         }
     );
 }
@@ -22,7 +21,7 @@ fn insert_values() {
 fn insert_cols_values() {
     assert_eq!(
         test_sql("INSERT INTO tbl(col1, col2) VALUES (1, 2), (2, 3)",),
-        SqlMeta {
+        TableLineage {
             in_tables: vec![],
             out_tables: table("tbl")
         }
@@ -33,9 +32,9 @@ fn insert_cols_values() {
 fn insert_select_table() {
     assert_eq!(
         test_sql("INSERT INTO TEST SELECT * FROM TEMP",),
-        SqlMeta {
-            in_tables: table("TEMP"),
-            out_tables: table("TEST")
+        TableLineage {
+            in_tables: table("temp"),
+            out_tables: table("test")
         }
     );
 }
@@ -56,7 +55,7 @@ fn insert_select_table_2() {
             group by to_date(C_AT), dm.C_NAME, P
             ;",
         ),
-        SqlMeta {
+        TableLineage {
             in_tables: tables(vec!["b1.b2", "m.dim"]),
             out_tables: table("a1.a2")
         }
@@ -73,7 +72,7 @@ fn insert_nested_select() {
                 FROM top_delivery_times
                 GROUP BY order_placed_on;
             ",
-    ), SqlMeta {
+    ), TableLineage {
         in_tables: table("top_delivery_times"),
         out_tables: table("popular_orders_day_of_week")
     })
@@ -83,9 +82,9 @@ fn insert_nested_select() {
 fn insert_snowflake_table() {
     assert_eq!(
         test_sql_dialect("\n    INSERT INTO test_orders (ord, str, num) VALUES\n    (1, 'b', 15),\n    (2, 'a', 21),\n    (3, 'b', 7);\n   ", "snowflake"),
-        SqlMeta {
+        TableLineage {
             in_tables: vec![],
-            out_tables: table("test_orders")
+            out_tables: table("TEST_ORDERS")
         }
     )
 }
@@ -116,7 +115,7 @@ fn insert_overwrite_table() {
             pii_userid
     "
         ),
-        SqlMeta {
+        TableLineage {
             in_tables: table("schema.fpsm"),
             out_tables: table("schema.dps")
         }
@@ -135,7 +134,7 @@ fn insert_overwrite_subqueries() {
         FROM
         (SELECT * FROM table2) a"
         ),
-        SqlMeta {
+        TableLineage {
             in_tables: table("table2"),
             out_tables: table("mytable")
         }
@@ -159,7 +158,7 @@ fn insert_overwrite_multiple_subqueries() {
          SELECT * FROM table4) a
          "
         ),
-        SqlMeta {
+        TableLineage {
             in_tables: tables(vec!["table2", "table3", "table4"]),
             out_tables: table("mytable")
         }
@@ -185,7 +184,7 @@ fn insert_overwrite_partition() {
         ",
             "hive"
         ),
-        SqlMeta {
+        TableLineage {
             in_tables: tables(vec!["d_d_n.u_t_250", "d_n_p.g_d_r"]),
             out_tables: table("d_d_n.g_d_t_r")
         }
@@ -202,7 +201,7 @@ fn set_before_insert() {
             ",
             "hive"
         ),
-        SqlMeta {
+        TableLineage {
             in_tables: vec![],
             out_tables: table("a.b.c")
         }
@@ -236,7 +235,7 @@ fn insert_overwrite_hive_sets_large() {
         ",
             "hive"
         ),
-        SqlMeta {
+        TableLineage {
             in_tables: tables(vec!["d_p.f_p_s", "d_p.f_p_s_merged"]),
             out_tables: table("d_d_p.d_f_s")
         }
@@ -271,7 +270,7 @@ fn insert_group_by() {
               P,
               U_SOURCE;"
         ),
-        SqlMeta {
+        TableLineage {
             in_tables: table("b1.b2"),
             out_tables: table("a1.a2")
         }
@@ -385,7 +384,7 @@ fn insert_nested_with_select() {
           region,
           x;"
         ),
-        SqlMeta {
+        TableLineage {
             in_tables: tables(vec!["b1.b2", "c1.c2", "d1.d2", "e1.e2", "f1.f2"]),
             out_tables: table("a1.a2")
         }
@@ -401,7 +400,7 @@ fn test_multiple_statements_delete_insert() {
             INSERT INTO public.\"Employees\" VALUES (1, 'TALES OF SHIVA', 'Mark', 'mark', 0);
         "
         ),
-        SqlMeta {
+        TableLineage {
             in_tables: vec![],
             out_tables: table("public.\"Employees\""),
         }
@@ -417,7 +416,7 @@ fn test_multiple_statements_insert_insert() {
             INSERT INTO b.b VALUES(1,2);
         "
         ),
-        SqlMeta {
+        TableLineage {
             in_tables: vec![],
             out_tables: tables(vec!["a.a", "b.b"]),
         }
@@ -434,7 +433,7 @@ fn test_triple_statements_insert_insert_insert() {
             INSERT INTO c.c VALUES(1,2);
         "
         ),
-        SqlMeta {
+        TableLineage {
             in_tables: table("a.a"),
             out_tables: tables(vec!["a.a", "b.b", "c.c"]),
         }
@@ -498,7 +497,7 @@ fn insert_overwrite_multiple_unions() {
                 sub.u_i
         "
         ),
-        SqlMeta {
+        TableLineage {
             in_tables: tables(vec!["d_d_n.a_p_s_v", "d_d_n.d_p_s_v"]),
             out_tables: table("d_d_n.a_p_s_v")
         }
@@ -628,7 +627,7 @@ fn insert_overwrite_partition_dates() {
             sub.userkey
         "
         ),
-        SqlMeta {
+        TableLineage {
             in_tables: tables(vec!["ddw.aps2", "ddw.dps"]),
             out_tables: table("ddw.aps2")
         }
@@ -0,0 +1,240 @@
+// Copyright 2018-2022 contributors to the OpenLineage project
+// SPDX-License-Identifier: Apache-2.0
+
+use openlineage_sql::{DatasetOperation, DbTableMeta, Operation};
+
+#[macro_use]
+mod test_utils;
+use test_utils::*;
+
+#[test]
+fn drop_table() {
+    let meta = test_sql("DROP TABLE analytics.orders");
+    assert_eq!(
+        meta,
+        TableLineage {
+            in_tables: vec![],
+            out_tables: table("analytics.orders")
+        }
+    );
+    assert_eq!(
+        meta.operations,
+        vec![operation("analytics.orders", Operation::Drop)]
+    );
+}
+
+#[test]
+fn drop_multiple_tables_if_exists_cascade() {
+    assert_eq!(
+        test_sql("DROP TABLE IF EXISTS orders, items CASCADE").operations,
+        vec![
+            operation("items", Operation::Drop),
+            operation("orders", Operation::Drop),
+        ]
+    );
+}
+
+#[test]
+fn drop_view() {
+    assert_eq!(
+        test_sql("DROP VIEW IF EXISTS mart.recent").operations,
+        vec![operation("mart.recent", Operation::Drop)]
+    );
+}
+
+#[test]
+fn drop_other_objects() {
+    assert_eq!(test_sql("DROP SCHEMA staging CASCADE").operations, vec![]);
+    assert_eq!(test_sql("DROP INDEX orders_idx").operations, vec![]);
+}
+
+#[test]
+fn drop_cascades_to_views_of_script() {
+    assert_eq!(
+        test_multiple_sql(vec![
+            "CREATE VIEW recent AS SELECT * FROM orders WHERE day > now() - 7",
+            "CREATE VIEW other AS SELECT * FROM items",
+            "DROP TABLE orders CASCADE",
+        ])
+        .operations,
+        vec![
+            operation("orders", Operation::Drop),
+            operation("other", Operation::Create),
+            operation("recent", Operation::Create),
+            operation("recent", Operation::Drop),
+        ]
+    );
+}
+
+#[test]
+fn dropped_view_is_read_as_table() {
+    assert_eq!(
+        test_multiple_sql(vec![
+            "CREATE VIEW v AS SELECT * FROM t",
+            "DROP VIEW v",
+            "INSERT INTO report SELECT * FROM v",
+        ]),
+        TableLineage {
+            in_tables: tables(vec!["t", "v"]),
+            out_tables: tables(vec!["report", "v"])
+        }
+    );
+}
+
+#[test]
+fn drop_temporary_table_is_eliminated() {
+    assert_eq!(
+        test_multiple_sql(vec![
+            "CREATE TEMP TABLE tmp AS SELECT * FROM src",
+            "INSERT INTO dst SELECT * FROM tmp",
+            "DROP TABLE tmp",
+        ])
+        .operations,
+        vec![operation("dst", Operation::Append)]
+    );
+}
+
+#[test]
+fn truncate_table() {
+    let meta = test_sql("TRUNCATE TABLE analytics.orders");
+    assert_eq!(
+        meta,
+        TableLineage {
+            in_tables: vec![],
+            out_tables: table("analytics.orders")
+        }
+    );
+    assert_eq!(
+        meta.operations,
+        vec![operation("analytics.orders", Operation::Truncate)]
+    );
+}
+
+#[test]
+fn truncate_multiple_tables_with_options() {
+    assert_eq!(
+        test_sql("TRUNCATE ONLY orders, items * RESTART IDENTITY CASCADE").operations,
+        vec![
+            operation("items", Operation::Truncate),
+            operation("orders", Operation::Truncate),
+        ]
+    );
+}
+
+#[test]
+fn truncate_if_exists() {
+    assert_eq!(
+        test_sql_dialect("TRUNCATE TABLE IF EXISTS mart.orders", "snowflake").operations,
+        vec![DatasetOperation::new(
+            DbTableMeta::new_default_dialect(String::from("MART.ORDERS")),
+            Operation::Truncate
+        )]
+    );
+}
+
+#[test]
+fn truncate_hive_partition() {
+    assert_eq!(
+        test_sql_dialect("TRUNCATE TABLE logs PARTITION (day = '2022-01-01')", "hive").operations,
+        vec![operation("logs", Operation::Truncate)]
+    );
+}
+
+#[test]
+fn snowflake_undrop_table() {
+    let meta = test_sql_dialect("UNDROP TABLE mart.orders", "snowflake");
+    assert_eq!(
+        meta,
+        TableLineage {
+            in_tables: vec![],
+            out_tables: table("MART.ORDERS")
+        }
+    );
+    assert_eq!(
+        meta.operations,
+        vec![DatasetOperation::new(
+            DbTableMeta::new_default_dialect(String::from("MART.ORDERS")),
+            Operation::Undrop
+        )]
+    );
+    assert_eq!(
+        test_sql_dialect("UNDROP SCHEMA mart", "snowflake").operations,
+        vec![]
+    );
+}
+
+#[test]
+fn lifecycle_state_change_facets() {
+    let meta = test_multiple_sql(vec![
+        "TRUNCATE TABLE staging.orders",
+        "DROP TABLE mart.old_orders",
+        "ALTER TABLE mart.orders RENAME TO old_orders",
+        "INSERT INTO mart.log SELECT * FROM staging.orders",
+    ]);
+    let facets: Vec<(String, serde_json::Value)> = meta
+        .lifecycle_state_change_facets("postgres://localhost:5432", "producer")
+        .into_iter()
+        .map(|(table, facet)| {
+            (
+                table.qualified_name(),
+                serde_json::from_str(&facet.to_json()).unwrap(),
+            )
+        })
+        .collect();
+    let schema_url = "https://openlineage.io/spec/facets/1-0-0/LifecycleStateChangeDatasetFacet.json#/$defs/LifecycleStateChangeDatasetFacet";
+    assert_eq!(
+        facets,
+        vec![
+            (
+                String::from("mart.old_orders"),
+                serde_json::json!({
+                    "_producer": "producer",
+                    "_schemaURL": schema_url,
+                    "lifecycleStateChange": "RENAME",
+                    "previousIdentifier": {
+                        "namespace": "postgres://localhost:5432",
+                        "name": "mart.orders"
+                    }
+                })
+            ),
+            (
+                String::from("staging.orders"),
+                serde_json::json!({
+                    "_producer": "producer",
+                    "_schemaURL": schema_url,
+                    "lifecycleStateChange": "TRUNCATE"
+                })
+            ),
+        ]
+    );
+}
+
+fn lifecycle_states(sql: Vec<&str>) -> Vec<(String, String)> {
+    test_multiple_sql(sql)
+        .lifecycle_state_change_facets("postgres://localhost:5432", "producer")
+        .into_iter()
+        .map(|(table, facet)| (table.qualified_name(), facet.lifecycle_state_change))
+        .collect()
+}
+
+#[test]
+fn lifecycle_state_of_table_dropped_and_recreated() {
+    assert_eq!(
+        lifecycle_states(vec![
+            "DROP TABLE IF EXISTS mart.orders",
+            "CREATE TABLE mart.orders AS SELECT * FROM raw.orders",
+        ]),
+        vec![(String::from("mart.orders"), String::from("CREATE"))]
+    );
+}
+
+#[test]
+fn lifecycle_state_of_table_created_and_dropped() {
+    assert_eq!(
+        lifecycle_states(vec![
+            "CREATE TABLE mart.orders AS SELECT * FROM raw.orders",
+            "DROP TABLE mart.orders",
+        ]),
+        vec![(String::from("mart.orders"), String::from("DROP"))]
+    );
+}
@@ -0,0 +1,219 @@
+// Copyright 2018-2022 contributors to the OpenLineage project
+// SPDX-License-Identifier: Apache-2.0
+
+use std::sync::Arc;
+
+use openlineage_sql::{
+    get_dialect, parse_multiple_statements_with_options, ColumnLineage, NameField, NameMapping,
+    ParseOptions, SqlMeta,
+};
+
+#[macro_use]
+mod test_utils;
+use test_utils::*;
+
+fn test_sql_mapping(sql: &str, dialect: &str, mapping: NameMapping) -> SqlMeta {
+    let options = ParseOptions {
+        name_mapping: Some(Arc::new(mapping)),
+        ..ParseOptions::default()
+    };
+    parse_multiple_statements_with_options(vec![sql], get_dialect(dialect), &options).unwrap()
+}
+
+#[test]
+fn mapping_prefix() {
+    let mut mapping = NameMapping::new();
+    mapping.add_prefix(NameField::Schema, "dev_", "");
+    assert_eq!(
+        test_sql_mapping(
+            "INSERT INTO dev_mart.orders SELECT * FROM dev_raw.orders JOIN developers ON true",
+            "postgres",
+            mapping
+        ),
+        TableLineage {
+            in_tables: tables(vec!["developers", "raw.orders"]),
+            out_tables: table("mart.orders")
+        }
+    );
+}
+
+#[test]
+fn mapping_suffix() {
+    let mut mapping = NameMapping::new();
+    mapping.add_suffix(NameField::Database, "_ALIAS", "");
+    mapping.add_suffix(NameField::Table, "_v", "");
+    assert_eq!(
+        test_sql_mapping(
+            "INSERT INTO ANALYTICS.MART.ORDERS SELECT * FROM ANALYTICS_ALIAS.MART.ORDERS_V",
+            "snowflake",
+            mapping
+        ),
+        TableLineage {
+            in_tables: table("ANALYTICS.MART.ORDERS"),
+            out_tables: table("ANALYTICS.MART.ORDERS")
+        }
+    );
+}
+
+#[test]
+fn mapping_regex() {
+    let mut mapping = NameMapping::new();
+    mapping.add_regex(r"^(dev|test)_(\w+)\.", "${2}.").unwrap();
+    assert_eq!(
+        test_sql_mapping(
+            "INSERT INTO test_mart.orders SELECT * FROM dev_raw.orders",
+            "postgres",
+            mapping
+        ),
+        TableLineage {
+            in_tables: table("raw.orders"),
+            out_tables: table("mart.orders")
+        }
+    );
+}
+
+#[test]
+fn mapping_regex_can_change_parts() {
+    let mut mapping = NameMapping::new();
+    mapping.add_regex(r"^staging_(\w+)$", "mart.$1").unwrap();
+    assert_eq!(
+        test_sql_mapping("SELECT * FROM staging_orders", "postgres", mapping),
+        TableLineage {
+            in_tables: table("mart.orders"),
+            out_tables: vec![]
+        }
+    );
+}
+
+#[test]
+fn mapping_invalid_regex() {
+    assert!(NameMapping::new().add_regex("(", "").is_err());
+}
+
+#[test]
+fn mapping_synonym_merges_tables() {
+    let mut mapping = NameMapping::new();
+    mapping.add_synonym("mart.Orders_View", "mart.orders");
+    assert_eq!(
+        test_sql_mapping(
+            "INSERT INTO report SELECT * FROM mart.orders_view UNION SELECT * FROM mart.orders",
+            "postgres",
+            mapping
+        ),
+        TableLineage {
+            in_tables: table("mart.orders"),
+            out_tables: table("report")
+        }
+    );
+}
+
+#[test]
+fn mapping_applies_after_rules() {
+    let mut mapping = NameMapping::new();
+    mapping.add_prefix(NameField::Schema, "dev_", "");
+    mapping.add_synonym("mart.orders_v", "mart.orders");
+    assert_eq!(
+        test_sql_mapping("SELECT * FROM dev_mart.orders_v", "postgres", mapping),
+        TableLineage {
+            in_tables: table("mart.orders"),
+            out_tables: vec![]
+        }
+    );
+}
+
+#[test]
+fn mapping_column_lineage() {
+    let mut mapping = NameMapping::new();
+    mapping.add_prefix(NameField::Schema, "dev_", "");
+    assert_eq!(
+        test_sql_mapping(
+            "INSERT INTO dev_mart.totals (amount) SELECT SUM(amount) FROM dev_raw.orders",
+            "postgres",
+            mapping
+        )
+        .column_lineage,
+        vec![ColumnLineage {
+            descendant: column("mart.totals", "amount"),
+            lineage: vec![column("raw.orders", "amount")]
+        }]
+    );
+}
+
+#[test]
+fn mapping_from_json() {
+    let mapping = NameMapping::from_json(
+        r#"{
+            "prefixes": [{"field": "schema", "from": "dev_"}],
+            "regex": [{"pattern": "^raw\\.", "replacement": "landing."}],
+            "synonyms": {"mart.orders_v": "mart.orders"}
+        }"#,
+    )
+    .unwrap();
+    assert_eq!(
+        test_sql_mapping(
+            "INSERT INTO dev_mart.orders_v SELECT * FROM dev_raw.orders",
+            "postgres",
+            mapping
+        ),
+        TableLineage {
+            in_tables: table("landing.orders"),
+            out_tables: table("mart.orders")
+        }
+    );
+}
+
+#[test]
+fn mapping_from_invalid_json() {
+    assert!(NameMapping::from_json(r#"{"prefix": []}"#).is_err());
+    assert!(
+        NameMapping::from_json(r#"{"prefixes": [{"field": "column", "from": "x"}]}"#).is_err()
+    );
+}
+
+#[test]
+fn mapping_quoted_name_with_dots() {
+    let mut mapping = NameMapping::new();
+    mapping.add_regex(r"\.orders$", ".orders_v2").unwrap();
+    mapping.add_synonym("\"my.schema\".items", "\"other.schema\".\"items.v1\"");
+    let meta = test_sql_mapping(
+        "INSERT INTO \"my.schema\".\"orders\" SELECT * FROM \"my.schema\".items",
+        "postgres",
+        mapping,
+    );
+    let names: Vec<(Option<String>, String, String, Vec<String>)> = meta
+        .in_tables
+        .iter()
+        .chain(meta.out_tables.iter())
+        .map(|t| {
+            (
+                t.schema.clone(),
+                t.name.clone(),
+                t.original_name.clone(),
+                t.parts.iter().map(|p| p.raw()).collect(),
+            )
+        })
+        .collect();
+    assert_eq!(
+        names,
+        vec![
+            (
+                Some(String::from("other.schema")),
+                String::from("items.v1"),
+                String::from("\"other.schema\".\"items.v1\""),
+                vec![
+                    String::from("\"other.schema\""),
+                    String::from("\"items.v1\"")
+                ]
+            ),
+            (
+                Some(String::from("my.schema")),
+                String::from("orders_v2"),
+                String::from("\"my.schema\".\"orders_v2\""),
+                vec![
+                    String::from("\"my.schema\""),
+                    String::from("\"orders_v2\"")
+                ]
+            ),
+        ]
+    );
+}
@@ -4,7 +4,7 @@
 #[macro_use]
 mod test_utils;
 
-use openlineage_sql::SqlMeta;
+use openlineage_sql::ColumnLineage;
 use test_utils::*;
 
 #[test]
@@ -32,19 +32,17 @@ fn merge_subquery_when_not_matched() {
             ,stg.C
         )",
         ),
-        SqlMeta {
+        TableLineage {
             in_tables: table("s.foo"),
             out_tables: table("s.bar")
         }
     );
 }
 
-// QUALIFY expression is not yet supported
-#[ignore]
 #[test]
 fn test_merge_multiple_clauses() {
     assert_eq!(
-        test_sql(
+        test_sql_dialect(
             "
             merge into \"m\".\"d\" as d_m using (
                 with f_d_u_l_u as (
@@ -74,11 +72,177 @@ fn test_merge_multiple_clauses() {
             d_m.c = src.c,
             d_m.z = src.z
             when not matched then insert (m_id,c_name,c_code,r_name,r_code,c,z)
-            values (m_id,c_name,c_code,r_name,r_code,c,z);"
+            values (m_id,c_name,c_code,r_name,r_code,c,z);",
+            "snowflake"
         ),
-        SqlMeta {
+        TableLineage {
             in_tables: table("c.u_l_u"),
             out_tables: table("m.d")
         }
     )
 }
+
+#[test]
+fn merge_source_with_cte() {
+    assert_eq!(
+        test_sql(
+            "MERGE INTO mart.orders t
+            USING (
+                WITH latest AS (SELECT * FROM raw.orders WHERE day = current_date)
+                SELECT l.id, l.amount, c.region FROM latest l JOIN raw.customers c ON l.cid = c.id
+            ) s
+            ON t.id = s.id
+            WHEN MATCHED THEN UPDATE SET amount = s.amount
+            WHEN NOT MATCHED THEN INSERT (id, amount, region) VALUES (s.id, s.amount, s.region)",
+        ),
+        TableLineage {
+            in_tables: tables(vec!["raw.customers", "raw.orders"]),
+            out_tables: table("mart.orders")
+        }
+    );
+}
+
+#[test]
+fn merge_subqueries_in_conditions() {
+    assert_eq!(
+        test_sql(
+            "MERGE INTO tgt t USING src s
+            ON t.id = s.id AND t.region IN (SELECT region FROM active_regions)
+            WHEN MATCHED AND EXISTS (SELECT 1 FROM blocked b WHERE b.id = s.id) THEN DELETE
+            WHEN MATCHED AND s.day > (SELECT MAX(day) FROM watermarks) THEN UPDATE SET x = s.x
+            WHEN NOT MATCHED AND s.kind IN (SELECT kind FROM kinds) THEN INSERT (id, x) VALUES (s.id, s.x)",
+        ),
+        TableLineage {
+            in_tables: tables(vec!["active_regions", "blocked", "kinds", "src", "watermarks"]),
+            out_tables: table("tgt")
+        }
+    );
+}
+
+#[test]
+fn merge_not_matched_by_source() {
+    let meta = test_sql_dialect(
+        "MERGE INTO dbo.tgt AS t USING dbo.src AS s ON t.id = s.id
+        WHEN MATCHED THEN UPDATE SET t.x = s.x
+        WHEN NOT MATCHED BY TARGET THEN INSERT (id, x) VALUES (s.id, s.x)
+        WHEN NOT MATCHED BY SOURCE AND t.active = 1 THEN DELETE;",
+        "mssql",
+    );
+    assert_eq!(
+        meta,
+        TableLineage {
+            in_tables: table("dbo.src"),
+            out_tables: table("dbo.tgt")
+        }
+    );
+    assert_eq!(
+        meta.column_lineage,
+        vec![
+            ColumnLineage {
+                descendant: column("dbo.tgt", "id"),
+                lineage: vec![column("dbo.src", "id")]
+            },
+            ColumnLineage {
+                descendant: column("dbo.tgt", "x"),
+                lineage: vec![column("dbo.src", "x")]
+            },
+        ]
+    );
+}
+
+#[test]
+fn merge_not_matched_by_source_update() {
+    assert_eq!(
+        test_sql_dialect(
+            "MERGE INTO tgt t USING src s ON t.id = s.id
+            WHEN NOT MATCHED BY SOURCE THEN UPDATE SET active = false",
+            "hive"
+        ),
+        TableLineage {
+            in_tables: table("src"),
+            out_tables: table("tgt")
+        }
+    );
+}
+
+#[test]
+fn merge_source_with_qualify() {
+    assert_eq!(
+        test_sql_dialect(
+            "MERGE INTO mart.users t
+            USING (
+                SELECT id, name FROM raw.users
+                QUALIFY ROW_NUMBER() OVER (PARTITION BY id ORDER BY updated_at DESC) = 1
+            ) s
+            ON t.id = s.id
+            WHEN MATCHED THEN UPDATE SET name = s.name",
+            "snowflake"
+        )
+        .column_lineage,
+        vec![ColumnLineage {
+            descendant: column("MART.USERS", "name"),
+            lineage: vec![column("RAW.USERS", "name")]
+        }]
+    );
+}
+
+#[test]
+fn merge_source_with_qualify_databricks() {
+    assert_eq!(
+        test_sql_dialect(
+            "MERGE INTO mart.users t
+            USING (
+                SELECT id, name FROM raw.users
+                QUALIFY ROW_NUMBER() OVER (PARTITION BY id ORDER BY updated_at DESC) = 1
+            ) s
+            ON t.id = s.id
+            WHEN MATCHED THEN UPDATE SET name = s.name
+            WHEN NOT MATCHED BY SOURCE THEN DELETE",
+            "databricks"
+        ),
+        TableLineage {
+            in_tables: table("raw.users"),
+            out_tables: table("mart.users")
+        }
+    );
+}
+
+#[test]
+fn qualify_after_having() {
+    assert_eq!(
+        test_sql_dialect(
+            "SELECT region, SUM(amount) AS total FROM orders GROUP BY region \
+            HAVING SUM(amount) > 0 QUALIFY RANK() OVER (ORDER BY SUM(amount) DESC) <= 10",
+            "bigquery"
+        ),
+        TableLineage {
+            in_tables: table("orders"),
+            out_tables: vec![]
+        }
+    );
+}
+
+#[test]
+fn qualify_is_a_name_in_postgres() {
+    assert_eq!(
+        test_sql("SELECT qualify FROM rules"),
+        TableLineage {
+            in_tables: table("rules"),
+            out_tables: vec![]
+        }
+    );
+}
+
+#[test]
+fn qualify_is_a_name_in_generic_dialect() {
+    assert_eq!(
+        test_sql_dialect(
+            "INSERT INTO checks SELECT r.qualify FROM rules r WHERE r.qualify = 1 ORDER BY qualify",
+            "generic"
+        ),
+        TableLineage {
+            in_tables: table("rules"),
+            out_tables: table("checks")
+        }
+    );
+}
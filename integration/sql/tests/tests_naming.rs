@@ -0,0 +1,168 @@
+// Copyright 2018-2022 contributors to the OpenLineage project
+// SPDX-License-Identifier: Apache-2.0
+
+use openlineage_sql::{Connection, DatasetName, DbTableMeta};
+
+fn dataset_name(connection: &Connection, table: &str) -> Result<DatasetName, String> {
+    connection.dataset_name(&DbTableMeta::new_default_dialect(String::from(table)))
+}
+
+fn name(namespace: &str, name: &str) -> Result<DatasetName, String> {
+    Ok(DatasetName {
+        namespace: String::from(namespace),
+        name: String::from(name),
+    })
+}
+
+#[test]
+fn naming_postgres() {
+    let connection = Connection {
+        host: Some(String::from("db.example.com")),
+        database: Some(String::from("shop")),
+        ..Connection::new("postgres")
+    };
+    assert_eq!(
+        dataset_name(&connection, "public.orders"),
+        name("postgres://db.example.com:5432", "shop.public.orders")
+    );
+    assert_eq!(
+        dataset_name(&connection, "other.public.orders"),
+        name("postgres://db.example.com:5432", "other.public.orders")
+    );
+    assert_eq!(
+        dataset_name(&connection, "orders"),
+        Err(String::from(
+            "postgres name requires schema, which table orders doesn't specify"
+        ))
+    );
+}
+
+#[test]
+fn naming_mysql() {
+    let connection = Connection {
+        host: Some(String::from("mysql")),
+        port: Some(3307),
+        ..Connection::new("mysql")
+    };
+    assert_eq!(
+        dataset_name(&connection, "shop.orders"),
+        name("mysql://mysql:3307", "shop.orders")
+    );
+    assert_eq!(
+        dataset_name(&connection, "a.shop.orders"),
+        Err(String::from("table a.shop.orders has too many parts for mysql"))
+    );
+}
+
+#[test]
+fn naming_redshift() {
+    let connection = Connection {
+        host: Some(String::from(
+            "examplecluster.abc123xyz789.us-west-2.redshift.amazonaws.com",
+        )),
+        database: Some(String::from("dev")),
+        ..Connection::new("redshift")
+    };
+    assert_eq!(
+        dataset_name(&connection, "public.orders"),
+        name(
+            "redshift://examplecluster.us-west-2:5439",
+            "dev.public.orders"
+        )
+    );
+
+    let connection = Connection {
+        host: Some(String::from("examplecluster")),
+        region: Some(String::from("eu-west-1")),
+        port: Some(5440),
+        ..Connection::new("redshift")
+    };
+    assert_eq!(
+        connection.namespace(),
+        Ok(String::from("redshift://examplecluster.eu-west-1:5440"))
+    );
+}
+
+#[test]
+fn naming_snowflake() {
+    let connection = Connection {
+        account: Some(String::from("xy12345")),
+        ..Connection::new("snowflake")
+    };
+    assert_eq!(
+        dataset_name(&connection, "DB.PUBLIC.ORDERS"),
+        name("snowflake://xy12345", "DB.PUBLIC.ORDERS")
+    );
+
+    let connection = Connection {
+        host: Some(String::from("xy12345.us-east-1.snowflakecomputing.com")),
+        ..Connection::new("snowflake")
+    };
+    assert_eq!(
+        connection.namespace(),
+        Ok(String::from("snowflake://xy12345.us-east-1"))
+    );
+}
+
+#[test]
+fn naming_bigquery() {
+    let connection = Connection {
+        project: Some(String::from("my-project")),
+        ..Connection::new("bigquery")
+    };
+    assert_eq!(
+        dataset_name(&connection, "dataset.orders"),
+        name("bigquery", "my-project.dataset.orders")
+    );
+    assert_eq!(
+        dataset_name(&connection, "other-project.dataset.orders"),
+        name("bigquery", "other-project.dataset.orders")
+    );
+}
+
+#[test]
+fn naming_hive() {
+    let connection = Connection {
+        host: Some(String::from("hive-server")),
+        ..Connection::new("hive")
+    };
+    assert_eq!(
+        dataset_name(&connection, "default.orders"),
+        name("hive://hive-server:10000", "default.orders")
+    );
+}
+
+#[test]
+fn naming_sqlserver() {
+    let connection = Connection {
+        host: Some(String::from("pool.sql.azuresynapse.net")),
+        database: Some(String::from("SQLPool1")),
+        ..Connection::new("sqlserver")
+    };
+    assert_eq!(
+        dataset_name(&connection, "dbo.orders"),
+        name(
+            "sqlserver://pool.sql.azuresynapse.net:1433;database=SQLPool1;",
+            "dbo.orders"
+        )
+    );
+    assert_eq!(
+        dataset_name(&connection, "Sales.dbo.orders"),
+        name(
+            "sqlserver://pool.sql.azuresynapse.net:1433;database=Sales;",
+            "dbo.orders"
+        )
+    );
+}
+
+#[test]
+fn naming_errors() {
+    assert_eq!(
+        Connection::new("postgres").namespace(),
+        Err(String::from("postgres namespace requires host"))
+    );
+    assert_eq!(
+        Connection::new("oracle").namespace(),
+        Err(String::from("unsupported scheme oracle"))
+    );
+}
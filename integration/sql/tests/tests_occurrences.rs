@@ -0,0 +1,185 @@
+// Copyright 2018-2022 contributors to the OpenLineage project
+// SPDX-License-Identifier: Apache-2.0
+
+use openlineage_sql::{
+    get_dialect, parse_multiple_statements_with_options, parse_statements_with_options,
+    ParseOptions, SqlMeta, TableRole,
+};
+
+#[macro_use]
+mod test_utils;
+use test_utils::*;
+
+// Role, table name, line and column where it starts, and text it spans.
+fn occurrences<'a>(
+    sql: &'a str,
+    meta: &SqlMeta,
+) -> Vec<(TableRole, String, u64, u64, &'a str)> {
+    meta.occurrences
+        .iter()
+        .map(|o| {
+            (
+                o.role,
+                o.table.qualified_name(),
+                o.start.line,
+                o.start.column,
+                &sql[o.start.offset..o.end.offset],
+            )
+        })
+        .collect()
+}
+
+#[test]
+fn occurrences_of_inputs_and_outputs() {
+    let sql = "INSERT INTO mart.totals\nSELECT o.id, SUM(i.amount)\nFROM raw.orders o\n\
+        JOIN raw.items i ON o.id = i.order_id GROUP BY o.id";
+    assert_eq!(
+        occurrences(sql, &test_sql(sql)),
+        vec![
+            (TableRole::Output, String::from("mart.totals"), 1, 13, "mart.totals"),
+            (TableRole::Input, String::from("raw.orders"), 3, 6, "raw.orders"),
+            (TableRole::Input, String::from("raw.items"), 4, 6, "raw.items"),
+        ]
+    );
+}
+
+#[test]
+fn occurrences_of_repeated_table() {
+    let sql = "SELECT * FROM orders WHERE id IN (SELECT orders.id FROM orders)";
+    assert_eq!(
+        occurrences(sql, &test_sql(sql)),
+        vec![
+            (TableRole::Input, String::from("orders"), 1, 15, "orders"),
+            (TableRole::Input, String::from("orders"), 1, 57, "orders"),
+        ]
+    );
+}
+
+#[test]
+fn occurrences_of_table_read_and_written() {
+    let sql = "INSERT INTO orders SELECT * FROM orders";
+    assert_eq!(
+        occurrences(sql, &test_sql(sql)),
+        vec![
+            (TableRole::Output, String::from("orders"), 1, 13, "orders"),
+            (TableRole::Input, String::from("orders"), 1, 34, "orders"),
+        ]
+    );
+}
+
+#[test]
+fn occurrences_of_quoted_name() {
+    let sql = "SELECT * FROM \"My Schema\".\"Orders\"";
+    assert_eq!(
+        occurrences(sql, &test_sql(sql)),
+        vec![(
+            TableRole::Input,
+            String::from("My Schema.Orders"),
+            1,
+            15,
+            "\"My Schema\".\"Orders\""
+        )]
+    );
+}
+
+#[test]
+fn occurrences_skip_ctes() {
+    let sql = "WITH recent AS (SELECT * FROM orders) SELECT * FROM recent";
+    assert_eq!(
+        occurrences(sql, &test_sql(sql)),
+        vec![(TableRole::Input, String::from("orders"), 1, 31, "orders")]
+    );
+}
+
+#[test]
+fn occurrences_are_resolved() {
+    let options = ParseOptions {
+        default_schema: Some(String::from("public")),
+        ..ParseOptions::default()
+    };
+    let sql = "SELECT * FROM orders";
+    let meta =
+        parse_multiple_statements_with_options(vec![sql], get_dialect("postgres"), &options)
+            .unwrap();
+    assert_eq!(
+        occurrences(sql, &meta),
+        vec![(TableRole::Input, String::from("public.orders"), 1, 15, "orders")]
+    );
+}
+
+#[test]
+fn occurrences_of_statements() {
+    let sql = "CREATE TABLE a (x int);\nINSERT INTO b SELECT * FROM a";
+    let statements =
+        parse_statements_with_options(vec![sql], get_dialect("postgres"), &ParseOptions::default())
+            .unwrap();
+    let occurrences: Vec<(usize, TableRole, String, u64, u64)> = statements
+        .iter()
+        .flat_map(|s| s.sql_meta.occurrences.iter())
+        .map(|o| {
+            (
+                o.statement_index,
+                o.role,
+                o.table.qualified_name(),
+                o.start.line,
+                o.start.column,
+            )
+        })
+        .collect();
+    assert_eq!(
+        occurrences,
+        vec![
+            (0, TableRole::Output, String::from("a"), 1, 14),
+            (1, TableRole::Output, String::from("b"), 2, 13),
+            (1, TableRole::Input, String::from("a"), 2, 29),
+        ]
+    );
+}
+
+#[test]
+fn occurrences_skip_temporary_tables() {
+    let sql = "CREATE TEMPORARY TABLE tmp AS SELECT * FROM src;\nINSERT INTO dst SELECT * FROM tmp";
+    let meta = test_multiple_sql(vec![sql]);
+    assert_eq!(
+        occurrences(sql, &meta),
+        vec![
+            (TableRole::Input, String::from("src"), 1, 45, "src"),
+            (TableRole::Output, String::from("dst"), 2, 13, "dst"),
+        ]
+    );
+}
+
+#[test]
+fn occurrences_skip_columns_and_aliases_named_like_table() {
+    let sql = "SELECT orders.id, orders, amount AS orders FROM orders ORDER BY orders";
+    assert_eq!(
+        occurrences(sql, &test_sql(sql)),
+        vec![(TableRole::Input, String::from("orders"), 1, 49, "orders")]
+    );
+}
+
+#[test]
+fn occurrences_in_list_of_tables() {
+    let sql = "SELECT a, b FROM a, b AS x (c1, c2), c WHERE a.id = b.id";
+    assert_eq!(
+        occurrences(sql, &test_sql(sql)),
+        vec![
+            (TableRole::Input, String::from("a"), 1, 18, "a"),
+            (TableRole::Input, String::from("b"), 1, 21, "b"),
+            (TableRole::Input, String::from("c"), 1, 38, "c"),
+        ]
+    );
+}
+
+#[test]
+fn occurrences_of_view_read_in_script() {
+    let sql = "CREATE VIEW v AS SELECT * FROM t;\nINSERT INTO report SELECT * FROM v";
+    assert_eq!(
+        occurrences(sql, &test_multiple_sql(vec![sql])),
+        vec![
+            (TableRole::Output, String::from("v"), 1, 13, "v"),
+            (TableRole::Input, String::from("t"), 1, 32, "t"),
+            (TableRole::Output, String::from("report"), 2, 13, "report"),
+        ]
+    );
+}
@@ -0,0 +1,88 @@
+// Copyright 2018-2022 contributors to the OpenLineage project
+// SPDX-License-Identifier: Apache-2.0
+
+use openlineage_sql::Operation;
+
+#[macro_use]
+mod test_utils;
+use test_utils::*;
+
+#[test]
+fn operation_insert_append() {
+    assert_eq!(
+        test_sql("INSERT INTO tgt SELECT * FROM src").operations,
+        vec![operation("tgt", Operation::Append)]
+    )
+}
+
+#[test]
+fn operation_insert_overwrite() {
+    assert_eq!(
+        test_sql_dialect(
+            "INSERT OVERWRITE TABLE tgt SELECT * FROM src",
+            "hive"
+        )
+        .operations,
+        vec![operation("tgt", Operation::Overwrite)]
+    )
+}
+
+#[test]
+fn operation_insert_on_duplicate_key_update() {
+    assert_eq!(
+        test_sql_dialect(
+            "INSERT INTO tgt (id, val) VALUES (1, 2) ON DUPLICATE KEY UPDATE val = 2",
+            "mysql"
+        )
+        .operations,
+        vec![operation("tgt", Operation::Upsert)]
+    )
+}
+
+#[test]
+fn operation_update_delete_merge() {
+    assert_eq!(
+        test_multiple_sql(vec![
+            "UPDATE a SET x = 1",
+            "DELETE FROM b WHERE x = 1",
+            "MERGE INTO c USING d ON c.id = d.id WHEN MATCHED THEN DELETE",
+        ])
+        .operations,
+        vec![
+            operation("a", Operation::Update),
+            operation("b", Operation::Delete),
+            operation("c", Operation::Upsert),
+        ]
+    )
+}
+
+#[test]
+fn operation_create() {
+    assert_eq!(
+        test_multiple_sql(vec![
+            "CREATE TABLE a AS SELECT * FROM src",
+            "CREATE OR REPLACE TABLE b AS SELECT * FROM src",
+            "SELECT * INTO c FROM src",
+            "INSERT INTO a SELECT * FROM src",
+        ])
+        .operations,
+        vec![
+            operation("a", Operation::Append),
+            operation("a", Operation::Create),
+            operation("b", Operation::CreateOrReplace),
+            operation("c", Operation::Create),
+        ]
+    )
+}
+
+#[test]
+fn operation_temporary_tables_skipped() {
+    assert_eq!(
+        test_multiple_sql(vec![
+            "CREATE TEMP TABLE tmp AS SELECT * FROM src",
+            "INSERT INTO tgt SELECT * FROM tmp",
+        ])
+        .operations,
+        vec![operation("tgt", Operation::Append)]
+    )
+}
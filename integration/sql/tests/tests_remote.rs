@@ -0,0 +1,154 @@
+// Copyright 2018-2022 contributors to the OpenLineage project
+// SPDX-License-Identifier: Apache-2.0
+
+use openlineage_sql::{
+    get_dialect, parse_sql, ColumnLineage, Connection, DatasetName, ParseError, ParseOptions,
+};
+
+#[macro_use]
+mod test_utils;
+use test_utils::*;
+
+#[test]
+fn remote_four_part_name() {
+    let meta = test_sql_dialect(
+        "INSERT INTO dbo.orders SELECT * FROM [LinkedSrv].[Sales].[dbo].[orders]",
+        "mssql",
+    );
+    assert_eq!(
+        meta,
+        TableLineage {
+            in_tables: table("LinkedSrv.Sales.dbo.orders"),
+            out_tables: table("dbo.orders")
+        }
+    );
+    assert_eq!(meta.in_tables[0].server, Some(String::from("LinkedSrv")));
+}
+
+#[test]
+fn remote_five_part_name() {
+    assert_eq!(
+        parse_sql("SELECT * FROM a.b.c.d.e", get_dialect("mssql"), None).unwrap_err(),
+        ParseError::Unsupported {
+            node: String::from("ObjectName"),
+            message: String::from("Table name a.b.c.d.e has more than four parts"),
+        }
+    );
+}
+
+#[test]
+fn remote_openquery() {
+    let meta = test_sql_dialect(
+        "INSERT INTO dbo.customers (id, name)
+        SELECT r.id, r.name FROM OPENQUERY(CRM, 'SELECT id, name FROM crm.dbo.customers') AS r",
+        "mssql",
+    );
+    assert_eq!(
+        meta,
+        TableLineage {
+            in_tables: table("CRM.crm.dbo.customers"),
+            out_tables: table("dbo.customers")
+        }
+    );
+    assert_eq!(
+        meta.column_lineage,
+        vec![
+            ColumnLineage {
+                descendant: column("dbo.customers", "id"),
+                lineage: vec![column("CRM.crm.dbo.customers", "id")]
+            },
+            ColumnLineage {
+                descendant: column("dbo.customers", "name"),
+                lineage: vec![column("CRM.crm.dbo.customers", "name")]
+            },
+        ]
+    );
+}
+
+#[test]
+fn remote_openrowset() {
+    assert_eq!(
+        test_sql_dialect(
+            "SELECT a.* FROM OPENROWSET('MSOLEDBSQL', 'Server=db2.example.com,1433;Trusted_Connection=yes;', 'SELECT * FROM Sales.dbo.orders') AS a
+            JOIN OPENROWSET('MSOLEDBSQL', 'Data Source=db3;Trusted_Connection=yes;', Sales.dbo.customers) AS c ON a.id = c.id",
+            "mssql",
+        ),
+        TableLineage {
+            in_tables: tables(vec![
+                "\"db2.example.com,1433\".Sales.dbo.orders",
+                "db3.Sales.dbo.customers"
+            ]),
+            out_tables: vec![]
+        }
+    );
+}
+
+#[test]
+fn remote_unknown_source() {
+    assert_eq!(
+        parse_sql(
+            "SELECT * FROM OPENQUERY(CRM, CONCAT('SELECT * FROM ', 'orders'))",
+            get_dialect("mssql"),
+            None
+        )
+        .unwrap_err(),
+        ParseError::Unsupported {
+            node: String::from("TableFactor::OPENQUERY"),
+            message: String::from("Can't tell what OPENQUERY reads from: OPENQUERY"),
+        }
+    );
+}
+
+#[test]
+fn remote_unparseable_query_tolerant() {
+    let options = ParseOptions {
+        tolerant: true,
+        ..ParseOptions::default()
+    };
+    let meta = test_sql_options(
+        "INSERT INTO dbo.orders
+        SELECT * FROM OPENQUERY(CRM, 'SELEC oops') AS r JOIN dbo.customers AS c ON true",
+        "mssql",
+        &options,
+    );
+    assert_eq!(
+        meta,
+        TableLineage {
+            in_tables: table("dbo.customers"),
+            out_tables: table("dbo.orders")
+        }
+    );
+    assert_eq!(meta.errors.len(), 1);
+    assert!(matches!(meta.errors[0].reason, ParseError::Syntax { .. }));
+}
+
+#[test]
+fn remote_dataset_name() {
+    let connection = Connection {
+        host: Some(String::from("db1.example.com")),
+        database: Some(String::from("Sales")),
+        ..Connection::new("sqlserver")
+    };
+    let meta = test_sql_dialect(
+        "SELECT * FROM OPENROWSET('MSOLEDBSQL', 'Server=db2.example.com,14330;', Sales.dbo.orders) JOIN db3.Crm.dbo.customers ON true",
+        "mssql",
+    );
+    let names: Vec<DatasetName> = meta
+        .in_tables
+        .iter()
+        .map(|table| connection.dataset_name(table).unwrap())
+        .collect();
+    assert_eq!(
+        names,
+        vec![
+            DatasetName {
+                namespace: String::from("sqlserver://db2.example.com:14330;database=Sales;"),
+                name: String::from("dbo.orders")
+            },
+            DatasetName {
+                namespace: String::from("sqlserver://db3:1433;database=Crm;"),
+                name: String::from("dbo.customers")
+            },
+        ]
+    );
+}
@@ -0,0 +1,148 @@
+// Copyright 2018-2022 contributors to the OpenLineage project
+// SPDX-License-Identifier: Apache-2.0
+
+use openlineage_sql::{DatasetOperation, DbTableMeta, Operation, TableRename};
+
+#[macro_use]
+mod test_utils;
+use test_utils::*;
+
+fn rename(from: &str, to: &str) -> TableRename {
+    TableRename {
+        from: DbTableMeta::new_default_dialect(String::from(from)),
+        to: DbTableMeta::new_default_dialect(String::from(to)),
+    }
+}
+
+#[test]
+fn alter_table_rename() {
+    let meta = test_sql("ALTER TABLE analytics.orders_new RENAME TO orders");
+    assert_eq!(
+        meta,
+        TableLineage {
+            in_tables: table("analytics.orders_new"),
+            out_tables: table("analytics.orders")
+        }
+    );
+    assert_eq!(
+        meta.renames,
+        vec![rename("analytics.orders_new", "analytics.orders")]
+    );
+    assert_eq!(
+        meta.operations,
+        vec![DatasetOperation::new(
+            DbTableMeta::new_default_dialect(String::from("analytics.orders")),
+            Operation::Rename
+        )]
+    );
+}
+
+#[test]
+fn alter_table_rename_to_current_schema_in_snowflake() {
+    assert_eq!(
+        test_sql_dialect("ALTER TABLE staging.orders RENAME TO mart.orders", "snowflake").renames,
+        vec![rename("STAGING.ORDERS", "MART.ORDERS")]
+    );
+    assert_eq!(
+        test_sql_dialect("ALTER TABLE staging.orders RENAME TO orders", "snowflake").renames,
+        vec![rename("STAGING.ORDERS", "ORDERS")]
+    );
+}
+
+#[test]
+fn alter_table_swap_with() {
+    let meta = test_sql_dialect(
+        "ALTER TABLE IF EXISTS mart.orders SWAP WITH mart.orders_staging",
+        "snowflake",
+    );
+    assert_eq!(
+        meta,
+        TableLineage {
+            in_tables: tables(vec!["MART.ORDERS", "MART.ORDERS_STAGING"]),
+            out_tables: tables(vec!["MART.ORDERS", "MART.ORDERS_STAGING"])
+        }
+    );
+    assert_eq!(
+        meta.renames,
+        vec![
+            rename("MART.ORDERS", "MART.ORDERS_STAGING"),
+            rename("MART.ORDERS_STAGING", "MART.ORDERS"),
+        ]
+    );
+}
+
+#[test]
+fn alter_table_set_schema() {
+    assert_eq!(
+        test_sql("ALTER TABLE db.staging.orders SET SCHEMA archive").renames,
+        vec![rename("db.staging.orders", "db.archive.orders")]
+    );
+}
+
+#[test]
+fn mysql_rename_table() {
+    let meta = test_sql_dialect(
+        "RENAME TABLE orders TO orders_old, orders_new TO orders",
+        "mysql",
+    );
+    assert_eq!(
+        meta.renames,
+        vec![
+            rename("orders", "orders_old"),
+            rename("orders_new", "orders"),
+        ]
+    );
+    assert_eq!(
+        meta,
+        TableLineage {
+            in_tables: tables(vec!["orders", "orders_new"]),
+            out_tables: tables(vec!["orders", "orders_old"])
+        }
+    );
+}
+
+#[test]
+fn mssql_sp_rename() {
+    assert_eq!(
+        test_sql_dialect("EXEC sp_rename 'dbo.orders_new', 'orders'", "mssql").renames,
+        vec![rename("dbo.orders_new", "dbo.orders")]
+    );
+    assert_eq!(
+        test_sql_dialect("sys.sp_rename N'dbo.orders_new', N'orders', 'OBJECT'", "mssql").renames,
+        vec![rename("dbo.orders_new", "dbo.orders")]
+    );
+}
+
+#[test]
+fn mssql_sp_rename_column() {
+    let meta = test_sql_dialect(
+        "EXECUTE sp_rename 'dbo.orders.amount', 'total', 'COLUMN'",
+        "mssql",
+    );
+    assert_eq!(meta.renames, vec![]);
+    assert_eq!(
+        meta,
+        TableLineage {
+            in_tables: vec![],
+            out_tables: vec![]
+        }
+    );
+}
+
+#[test]
+fn blue_green_swap_script() {
+    assert_eq!(
+        test_multiple_sql_dialect(
+            vec![
+                "CREATE TABLE mart.orders_staging AS SELECT * FROM raw.orders",
+                "ALTER TABLE mart.orders SWAP WITH mart.orders_staging",
+            ],
+            "snowflake"
+        )
+        .renames,
+        vec![
+            rename("MART.ORDERS", "MART.ORDERS_STAGING"),
+            rename("MART.ORDERS_STAGING", "MART.ORDERS"),
+        ]
+    );
+}
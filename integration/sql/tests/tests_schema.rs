@@ -0,0 +1,355 @@
+// Copyright 2018-2022 contributors to the OpenLineage project
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use openlineage_sql::{
+    get_dialect, parse_multiple_statements_with_options, Connection, DatasetName, DbTableMeta,
+    InMemorySchemaProvider, JsonSchemaProvider, ParseOptions, SchemaProvider, SqlMeta,
+};
+
+#[macro_use]
+mod test_utils;
+use test_utils::*;
+
+fn provider(tables: Vec<(&str, Vec<&str>)>) -> Arc<dyn SchemaProvider> {
+    let tables: HashMap<String, Vec<String>> = tables
+        .into_iter()
+        .map(|(name, columns)| {
+            (
+                String::from(name),
+                columns.into_iter().map(String::from).collect(),
+            )
+        })
+        .collect();
+    Arc::new(InMemorySchemaProvider::from(tables))
+}
+
+fn test_sql_schema(sql: &str, schema_provider: Arc<dyn SchemaProvider>) -> SqlMeta {
+    let options = ParseOptions {
+        schema_provider: Some(schema_provider),
+        ..ParseOptions::default()
+    };
+    parse_multiple_statements_with_options(vec![sql], get_dialect("postgres"), &options).unwrap()
+}
+
+#[test]
+fn schema_expand_wildcard() {
+    assert_eq!(
+        test_sql_schema(
+            "INSERT INTO tgt SELECT * FROM src",
+            provider(vec![("src", vec!["a", "b"])])
+        )
+        .column_lineage,
+        vec![
+            lineage(column("tgt", "a"), vec![column("src", "a")]),
+            lineage(column("tgt", "b"), vec![column("src", "b")]),
+        ]
+    )
+}
+
+#[test]
+fn schema_expand_qualified_wildcard_through_cte() {
+    assert_eq!(
+        test_sql_schema(
+            "
+            WITH o AS (SELECT * FROM orders)
+            INSERT INTO tgt
+            SELECT o.*, c.name FROM o JOIN customers c ON o.customer_id = c.id",
+            provider(vec![("orders", vec!["id", "customer_id"])])
+        )
+        .column_lineage,
+        vec![
+            lineage(
+                column("tgt", "customer_id"),
+                vec![column("orders", "customer_id")]
+            ),
+            lineage(column("tgt", "id"), vec![column("orders", "id")]),
+            lineage(column("tgt", "name"), vec![column("customers", "name")]),
+        ]
+    )
+}
+
+#[test]
+fn schema_resolve_unqualified_columns_in_join() {
+    assert_eq!(
+        test_sql_schema(
+            "
+            INSERT INTO tgt
+            SELECT amount, name
+            FROM orders JOIN customers ON orders.customer_id = customers.id",
+            provider(vec![
+                ("orders", vec!["id", "customer_id", "amount"]),
+                ("customers", vec!["id", "name"])
+            ])
+        )
+        .column_lineage,
+        vec![
+            lineage(column("tgt", "amount"), vec![column("orders", "amount")]),
+            lineage(column("tgt", "name"), vec![column("customers", "name")]),
+        ]
+    )
+}
+
+#[test]
+fn schema_insert_target_columns() {
+    assert_eq!(
+        test_sql_schema(
+            "INSERT INTO tgt SELECT a, b FROM src",
+            provider(vec![("tgt", vec!["x", "y"])])
+        )
+        .column_lineage,
+        vec![
+            lineage(column("tgt", "x"), vec![column("src", "a")]),
+            lineage(column("tgt", "y"), vec![column("src", "b")]),
+        ]
+    )
+}
+
+#[test]
+fn schema_resolve_unqualified_tables() {
+    assert_eq!(
+        test_sql_schema(
+            "INSERT INTO mart.report SELECT * FROM orders",
+            provider(vec![
+                ("analytics.public.orders", vec!["id"]),
+                ("analytics.mart.report", vec!["id"]),
+                ("analytics.mart.orders", vec!["id"]),
+            ])
+        ),
+        TableLineage {
+            // orders is ambiguous, so it's left as it is
+            in_tables: table("orders"),
+            out_tables: table("analytics.mart.report")
+        }
+    )
+}
+
+#[test]
+fn schema_resolve_with_default_schema() {
+    let options = ParseOptions {
+        default_schema: Some(String::from("public")),
+        schema_provider: Some(provider(vec![
+            ("analytics.public.orders", vec!["id"]),
+            ("analytics.mart.orders", vec!["id"]),
+        ])),
+        ..ParseOptions::default()
+    };
+    assert_eq!(
+        parse_multiple_statements_with_options(
+            vec!["SELECT * FROM orders"],
+            get_dialect("postgres"),
+            &options
+        )
+        .unwrap(),
+        TableLineage {
+            in_tables: table("analytics.public.orders"),
+            out_tables: vec![]
+        }
+    )
+}
+
+#[test]
+fn schema_json_provider() {
+    let path = std::env::temp_dir().join("openlineage_sql_tests_schema.json");
+    std::fs::write(&path, r#"{"db.public.src": ["a", "b"]}"#).unwrap();
+    let provider = JsonSchemaProvider::from_file(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+
+    assert_eq!(
+        provider.get_columns(&DbTableMeta::new_default_dialect(String::from("src"))),
+        Some(vec![String::from("a"), String::from("b")])
+    );
+    assert_eq!(
+        provider.resolve_table(&DbTableMeta::new_default_dialect(String::from("public.src"))),
+        Some(DbTableMeta::new_default_dialect(String::from("db.public.src")))
+    );
+    assert!(JsonSchemaProvider::from_json("[1, 2]").is_err());
+}
+
+#[test]
+fn schema_case_insensitive_lookup() {
+    assert_eq!(
+        parse_multiple_statements_with_options(
+            vec!["INSERT INTO tgt SELECT * FROM orders"],
+            get_dialect("snowflake"),
+            &ParseOptions {
+                schema_provider: Some(provider(vec![("analytics.public.orders", vec!["id"])])),
+                ..ParseOptions::default()
+            }
+        )
+        .unwrap()
+        .column_lineage,
+        vec![lineage(
+            column("TGT", "id"),
+            vec![column("ANALYTICS.PUBLIC.ORDERS", "id")]
+        )]
+    )
+}
+
+#[test]
+fn schema_default_database() {
+    let options = ParseOptions {
+        default_database: Some(String::from("PROD")),
+        default_schema: Some(String::from("PUBLIC")),
+        ..ParseOptions::default()
+    };
+    assert_eq!(
+        test_sql_options(
+            "INSERT INTO mart.orders SELECT * FROM raw_orders",
+            "snowflake",
+            &options
+        ),
+        TableLineage {
+            in_tables: table("PROD.PUBLIC.RAW_ORDERS"),
+            out_tables: table("PROD.MART.ORDERS")
+        }
+    )
+}
+
+#[test]
+fn schema_default_database_folded_like_identifiers() {
+    let options = ParseOptions {
+        default_database: Some(String::from("prod")),
+        default_schema: Some(String::from("public")),
+        ..ParseOptions::default()
+    };
+    let lineage = test_sql_options("SELECT * FROM orders", "snowflake", &options);
+    assert_eq!(lineage.in_tables, table("PROD.PUBLIC.ORDERS"));
+    assert_eq!(
+        Connection {
+            account: Some(String::from("xy12345")),
+            ..Connection::new("snowflake")
+        }
+        .dataset_name(&lineage.in_tables[0]),
+        Ok(DatasetName {
+            namespace: String::from("snowflake://xy12345"),
+            name: String::from("PROD.PUBLIC.ORDERS"),
+        })
+    );
+}
+
+#[test]
+fn schema_default_database_is_schema_in_mysql_and_hive() {
+    let options = ParseOptions {
+        default_database: Some(String::from("shop")),
+        ..ParseOptions::default()
+    };
+    for (dialect, port) in [("mysql", 3306), ("hive", 10000)] {
+        let lineage = test_sql_options("SELECT * FROM orders JOIN other.items", dialect, &options);
+        assert_eq!(lineage.in_tables, tables(vec!["other.items", "shop.orders"]));
+        assert_eq!(
+            Connection {
+                host: Some(String::from("db")),
+                ..Connection::new(dialect)
+            }
+            .dataset_name(&lineage.in_tables[1]),
+            Ok(DatasetName {
+                namespace: format!("{}://db:{}", dialect, port),
+                name: String::from("shop.orders"),
+            })
+        );
+    }
+}
+
+#[test]
+fn schema_default_database_keeps_explicit_database() {
+    let options = ParseOptions {
+        default_database: Some(String::from("my-project")),
+        ..ParseOptions::default()
+    };
+    assert_eq!(
+        test_sql_options(
+            "SELECT * FROM other-project.dataset.orders JOIN dataset.customers USING (id)",
+            "bigquery",
+            &options
+        ),
+        TableLineage {
+            in_tables: tables(vec![
+                "my-project.dataset.customers",
+                "other-project.dataset.orders"
+            ]),
+            out_tables: vec![]
+        }
+    )
+}
+
+#[test]
+fn schema_default_database_needs_schema() {
+    let options = ParseOptions {
+        default_database: Some(String::from("db")),
+        ..ParseOptions::default()
+    };
+    assert_eq!(
+        test_sql_options("SELECT * FROM orders", "postgres", &options),
+        TableLineage {
+            in_tables: table("orders"),
+            out_tables: vec![]
+        }
+    )
+}
+
+#[test]
+fn schema_search_path_without_provider() {
+    let options = ParseOptions {
+        default_schema: Some(String::from("ignored")),
+        search_path: vec![String::from("staging"), String::from("public")],
+        ..ParseOptions::default()
+    };
+    assert_eq!(
+        test_sql_options(
+            "INSERT INTO orders SELECT * FROM public.raw_orders",
+            "postgres",
+            &options
+        ),
+        TableLineage {
+            in_tables: table("public.raw_orders"),
+            out_tables: table("staging.orders")
+        }
+    )
+}
+
+#[test]
+fn schema_search_path_with_provider() {
+    let options = ParseOptions {
+        default_database: Some(String::from("analytics")),
+        search_path: vec![
+            String::from("staging"),
+            String::from("mart"),
+            String::from("public"),
+        ],
+        schema_provider: Some(provider(vec![
+            ("public.orders", vec!["id"]),
+            ("mart.orders", vec!["id"]),
+            ("public.customers", vec!["id", "name"]),
+        ])),
+        ..ParseOptions::default()
+    };
+    let meta = parse_multiple_statements_with_options(
+        vec!["INSERT INTO report SELECT c.* FROM orders o JOIN customers c ON o.id = c.id"],
+        get_dialect("postgres"),
+        &options,
+    )
+    .unwrap();
+    assert_eq!(
+        meta,
+        TableLineage {
+            in_tables: tables(vec!["analytics.mart.orders", "analytics.public.customers"]),
+            out_tables: table("analytics.staging.report")
+        }
+    );
+    assert_eq!(
+        meta.column_lineage,
+        vec![
+            lineage(
+                column("analytics.staging.report", "id"),
+                vec![column("analytics.public.customers", "id")]
+            ),
+            lineage(
+                column("analytics.staging.report", "name"),
+                vec![column("analytics.public.customers", "name")]
+            ),
+        ]
+    )
+}
@@ -1,7 +1,7 @@
 // Copyright 2018-2022 contributors to the OpenLineage project
 // SPDX-License-Identifier: Apache-2.0
 
-use openlineage_sql::{parse_sql, BigQueryDialect, SqlMeta};
+use openlineage_sql::{get_dialect, parse_sql, BigQueryDialect, DbTableMeta, NamePart};
 use std::sync::Arc;
 
 #[macro_use]
@@ -12,7 +12,7 @@ use test_utils::*;
 fn select_simple() {
     assert_eq!(
         test_sql("SELECT * FROM table0;",),
-        SqlMeta {
+        TableLineage {
             in_tables: table("table0"),
             out_tables: vec![]
         }
@@ -22,7 +22,7 @@ fn select_simple() {
 fn select_from_schema_table() {
     assert_eq!(
         test_sql("SELECT * FROM schema0.table0;",),
-        SqlMeta {
+        TableLineage {
             in_tables: table("schema0.table0"),
             out_tables: vec![]
         }
@@ -38,7 +38,7 @@ fn select_join() {
                 JOIN table1
                 ON t1.col0 = t2.col0",
         ),
-        SqlMeta {
+        TableLineage {
             in_tables: tables(vec!["table0", "table1"]),
             out_tables: vec![]
         }
@@ -55,7 +55,7 @@ fn select_inner_join() {
                 INNER JOIN table1
                 ON t1.col0 = t2.col0",
         ),
-        SqlMeta {
+        TableLineage {
             in_tables: tables(vec!["table0", "table1"]),
             out_tables: vec![]
         }
@@ -72,7 +72,7 @@ fn select_left_join() {
             LEFT JOIN table1
             ON t1.col0 = t2.col0",
         ),
-        SqlMeta {
+        TableLineage {
             in_tables: tables(vec!["table0", "table1"]),
             out_tables: vec![]
         }
@@ -92,7 +92,7 @@ fn select_bigquery_excaping() {
             None
         )
         .unwrap(),
-        SqlMeta {
+        TableLineage {
             in_tables: table("random-project.dbt_test1.source_table"),
             out_tables: vec![]
         }
@@ -103,7 +103,7 @@ fn select_bigquery_excaping() {
 fn select_into() {
     assert_eq!(
         test_sql("SELECT * INTO table0 FROM table1;",),
-        SqlMeta {
+        TableLineage {
             in_tables: table("table1"),
             out_tables: table("table0")
         }
@@ -114,9 +114,205 @@ fn select_into() {
 fn select_redshift() {
     assert_eq!(
         test_sql_dialect("SELECT [col1] FROM [test_schema].[test_table]", "redshift"),
-        SqlMeta {
+        TableLineage {
             in_tables: table("test_schema.test_table"),
             out_tables: vec![]
         }
     )
 }
+
+#[test]
+fn select_case_folding_postgres() {
+    let meta = test_sql("SELECT * FROM orders JOIN ORDERS ON true JOIN \"Orders\" ON true");
+    assert_eq!(
+        meta,
+        TableLineage {
+            in_tables: tables(vec!["Orders", "orders"]),
+            out_tables: vec![]
+        }
+    );
+}
+
+#[test]
+fn select_case_folding_snowflake() {
+    let meta = test_sql_dialect(
+        "SELECT * FROM db.sch.orders JOIN DB.SCH.ORDERS ON true JOIN \"DB\".\"SCH\".\"ORDERS\" ON true",
+        "snowflake",
+    );
+    assert_eq!(meta.in_tables, table("DB.SCH.ORDERS"));
+    assert_eq!(meta.in_tables[0].original_name, "db.sch.orders");
+}
+
+#[test]
+fn select_case_folding_hive() {
+    let meta = test_sql_dialect("SELECT * FROM `Db`.`T` JOIN db.t ON true", "hive");
+    assert_eq!(meta.in_tables, table("db.t"));
+}
+
+#[test]
+fn select_case_preserved_bigquery() {
+    assert_eq!(
+        test_sql_dialect(
+            "SELECT * FROM project.Dataset.`Orders` JOIN project.Dataset.orders ON true",
+            "bigquery"
+        )
+        .in_tables,
+        tables(vec!["project.Dataset.Orders", "project.Dataset.orders"])
+    );
+}
+
+#[test]
+fn select_quoted_name_with_dots_and_quotes() {
+    let meta = test_sql("SELECT * FROM \"my.schema\".\"we\"\"ird\"");
+    assert_eq!(
+        meta.in_tables,
+        vec![DbTableMeta {
+            server: None,
+            database: None,
+            schema: Some(String::from("my.schema")),
+            name: String::from("we\"ird"),
+            partitions: vec![],
+            wildcard: false,
+            parts: vec![],
+            original_name: String::from("\"my.schema\".\"we\"\"ird\""),
+        }]
+    );
+}
+
+#[test]
+fn select_bigquery_quoted_path() {
+    assert_eq!(
+        test_sql_dialect(
+            "SELECT * FROM `my-project.dataset.orders` JOIN `my-project.dataset`.customers USING (id)",
+            "bigquery"
+        )
+        .in_tables,
+        tables(vec![
+            "my-project.dataset.customers",
+            "my-project.dataset.orders"
+        ])
+    );
+}
+
+#[test]
+fn select_bigquery_wildcard_table() {
+    let meta = test_sql_dialect(
+        "INSERT INTO ds.daily SELECT _TABLE_SUFFIX AS day, COUNT(*) AS cnt
+        FROM `proj-1.ds.events_*` WHERE _TABLE_SUFFIX BETWEEN '20220101' AND '20220131'
+        GROUP BY 1",
+        "bigquery",
+    );
+    assert_eq!(
+        meta,
+        TableLineage {
+            in_tables: table("proj-1.ds.events_*"),
+            out_tables: table("ds.daily")
+        }
+    );
+    assert!(meta.in_tables[0].wildcard);
+    assert!(!meta.out_tables[0].wildcard);
+}
+
+#[test]
+fn select_bigquery_partition_decorator() {
+    let meta = test_sql_dialect(
+        "SELECT * FROM proj-1.ds.events$20220101
+        UNION ALL SELECT * FROM `proj-1.ds.events$20220102`
+        UNION ALL SELECT * FROM proj-1.ds.events",
+        "bigquery",
+    );
+    assert_eq!(
+        meta.in_tables,
+        table("proj-1.ds.events")
+    );
+    assert_eq!(meta.in_tables[0].partitions, Vec::<String>::new());
+}
+
+#[test]
+fn select_bigquery_partition_decorators() {
+    let meta = test_sql_dialect(
+        "SELECT * FROM proj-1.ds.events$20220102
+        UNION ALL SELECT * FROM `proj-1.ds.events$20220101`
+        UNION ALL SELECT * FROM proj-1.ds.events$20220102",
+        "bigquery",
+    );
+    assert_eq!(
+        meta.in_tables,
+        table("proj-1.ds.events")
+    );
+    assert_eq!(
+        meta.in_tables[0].partitions,
+        vec![String::from("20220101"), String::from("20220102")]
+    );
+}
+
+#[test]
+fn select_bigquery_partitions_in_script() {
+    let meta = test_multiple_sql_dialect(
+        vec![
+            "INSERT INTO ds.daily SELECT * FROM ds.events$20220101",
+            "INSERT INTO ds.daily SELECT * FROM ds.events$20220102",
+        ],
+        "bigquery",
+    );
+    assert_eq!(
+        meta.in_tables[0].partitions,
+        vec![String::from("20220101"), String::from("20220102")]
+    );
+}
+
+#[test]
+fn select_bigquery_unquoted_wildcard_table() {
+    let meta = test_sql_dialect(
+        "SELECT * FROM proj-1.ds.events_* WHERE _TABLE_SUFFIX > '20220101'",
+        "bigquery",
+    );
+    assert_eq!(
+        meta.in_tables,
+        table("proj-1.ds.events_*")
+    );
+    assert!(meta.in_tables[0].wildcard);
+}
+
+#[test]
+fn select_name_parts_keep_quoting() {
+    let meta = test_sql_dialect("SELECT * FROM sales.\"Orders\"", "postgres");
+    let table = &meta.in_tables[0];
+    assert_eq!(
+        table.parts,
+        vec![
+            NamePart {
+                value: String::from("sales"),
+                quote_style: None
+            },
+            NamePart {
+                value: String::from("Orders"),
+                quote_style: Some('"')
+            },
+        ]
+    );
+    assert_eq!(table.parts[1].raw(), "\"Orders\"");
+    assert_eq!(
+        table.quoted_name(get_dialect("postgres").as_ref()),
+        "sales.\"Orders\""
+    );
+    assert_eq!(
+        table.quoted_name(get_dialect("snowflake").as_ref()),
+        "\"sales\".\"Orders\""
+    );
+}
+
+#[test]
+fn select_quoted_name_per_dialect() {
+    let meta = test_sql_dialect("SELECT * FROM [my db].dbo.[order]", "mssql");
+    let table = &meta.in_tables[0];
+    assert_eq!(
+        table.quoted_name(get_dialect("mssql").as_ref()),
+        "[my db].dbo.[order]"
+    );
+    assert_eq!(
+        table.quoted_name(get_dialect("bigquery").as_ref()),
+        "`my db`.dbo.`order`"
+    );
+    assert_eq!(table.parts[0].raw(), "[my db]");
+}
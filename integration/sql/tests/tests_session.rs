@@ -0,0 +1,192 @@
+// Copyright 2018-2022 contributors to the OpenLineage project
+// SPDX-License-Identifier: Apache-2.0
+
+use openlineage_sql::{
+    get_dialect, parse_multiple_statements_with_options, parse_statements_with_options,
+    ParseOptions, SqlMeta,
+};
+
+#[macro_use]
+mod test_utils;
+use test_utils::*;
+
+fn test_script(sql: &str, dialect: &str, default_schema: Option<&str>) -> SqlMeta {
+    let options = ParseOptions {
+        default_schema: default_schema.map(String::from),
+        ..ParseOptions::default()
+    };
+    parse_multiple_statements_with_options(vec![sql], get_dialect(dialect), &options).unwrap()
+}
+
+#[test]
+fn session_use_database_and_schema() {
+    assert_eq!(
+        test_script(
+            "
+            INSERT INTO raw_orders SELECT * FROM staging.orders;
+            USE DATABASE analytics;
+            USE SCHEMA mart;
+            INSERT INTO orders SELECT * FROM raw.orders;",
+            "snowflake",
+            Some("PUBLIC")
+        ),
+        TableLineage {
+            in_tables: tables(vec!["STAGING.ORDERS", "ANALYTICS.RAW.ORDERS"]),
+            out_tables: tables(vec!["PUBLIC.RAW_ORDERS", "ANALYTICS.MART.ORDERS"])
+        }
+    )
+}
+
+#[test]
+fn session_use_qualified_schema() {
+    assert_eq!(
+        test_script(
+            "USE SCHEMA analytics.\"Mart\"; USE WAREHOUSE etl_wh; SELECT * FROM orders",
+            "snowflake",
+            None
+        ),
+        TableLineage {
+            in_tables: table("ANALYTICS.Mart.ORDERS"),
+            out_tables: vec![]
+        }
+    )
+}
+
+#[test]
+fn session_use_mysql_database() {
+    assert_eq!(
+        test_script("USE shop; SELECT * FROM orders", "mysql", None),
+        TableLineage {
+            in_tables: table("shop.orders"),
+            out_tables: vec![]
+        }
+    )
+}
+
+#[test]
+fn session_use_mssql_database() {
+    assert_eq!(
+        test_script("USE [Sales]; SELECT * FROM dbo.orders", "mssql", None),
+        TableLineage {
+            in_tables: table("Sales.dbo.orders"),
+            out_tables: vec![]
+        }
+    )
+}
+
+#[test]
+fn session_set_search_path() {
+    assert_eq!(
+        test_script(
+            "
+            SET search_path TO \"$user\", Staging, 'Raw';
+            INSERT INTO orders SELECT * FROM public.src;
+            SET search_path = DEFAULT;
+            INSERT INTO totals SELECT * FROM orders;",
+            "postgres",
+            Some("public")
+        ),
+        TableLineage {
+            in_tables: tables(vec!["public.orders", "public.src"]),
+            out_tables: tables(vec!["public.totals", "staging.orders"])
+        }
+    )
+}
+
+#[test]
+fn session_set_schema() {
+    assert_eq!(
+        test_script(
+            "SET SESSION SCHEMA 'mart'; SELECT * FROM orders",
+            "postgres",
+            None
+        ),
+        TableLineage {
+            in_tables: table("mart.orders"),
+            out_tables: vec![]
+        }
+    )
+}
+
+#[test]
+fn session_alter_session() {
+    assert_eq!(
+        test_script(
+            "
+            ALTER SESSION SET QUERY_TAG = 'nightly';
+            ALTER SESSION SET CURRENT_SCHEMA = hr;
+            SELECT * FROM employees",
+            "generic",
+            None
+        ),
+        TableLineage {
+            in_tables: table("hr.employees"),
+            out_tables: vec![]
+        }
+    )
+}
+
+#[test]
+fn session_applies_across_sql_texts() {
+    let statements = parse_statements_with_options(
+        vec!["USE DATABASE prod", "SELECT * FROM public.orders"],
+        get_dialect("snowflake"),
+        &ParseOptions::default(),
+    )
+    .unwrap();
+    assert_eq!(statements.len(), 2);
+    assert_eq!(statements[0].sql_meta.in_tables, vec![]);
+    assert_eq!(
+        statements[1].sql_meta.in_tables,
+        table("PROD.PUBLIC.ORDERS")
+    );
+}
+
+#[test]
+fn session_other_variables_are_ignored() {
+    assert_eq!(
+        test_script(
+            "SET statement_timeout = 0; SELECT * FROM orders",
+            "postgres",
+            Some("public")
+        ),
+        TableLineage {
+            in_tables: table("public.orders"),
+            out_tables: vec![]
+        }
+    )
+}
+
+#[test]
+fn session_use_database_resets_schema() {
+    assert_eq!(
+        test_script(
+            "
+            USE SCHEMA staging;
+            INSERT INTO orders SELECT * FROM raw_orders;
+            USE DATABASE analytics;
+            INSERT INTO orders SELECT * FROM raw_orders;",
+            "snowflake",
+            None
+        ),
+        TableLineage {
+            in_tables: tables(vec!["STAGING.RAW_ORDERS", "ANALYTICS.PUBLIC.RAW_ORDERS"]),
+            out_tables: tables(vec!["STAGING.ORDERS", "ANALYTICS.PUBLIC.ORDERS"])
+        }
+    )
+}
+
+#[test]
+fn session_use_catalog_clears_schema() {
+    assert_eq!(
+        test_script(
+            "SET search_path TO staging; USE CATALOG analytics; SELECT * FROM orders",
+            "generic",
+            None
+        ),
+        TableLineage {
+            in_tables: table("orders"),
+            out_tables: vec![]
+        }
+    )
+}
@@ -0,0 +1,101 @@
+// Copyright 2018-2022 contributors to the OpenLineage project
+// SPDX-License-Identifier: Apache-2.0
+
+use openlineage_sql::{
+    get_dialect, parse_statements_with_options, DbTableMeta, ParseOptions, StatementMeta,
+};
+
+#[macro_use]
+mod test_utils;
+use test_utils::*;
+
+fn test_statements(sql: Vec<&str>, options: &ParseOptions) -> Vec<StatementMeta> {
+    parse_statements_with_options(sql, get_dialect("postgres"), options).unwrap()
+}
+
+fn edge(input: &str, output: &str) -> (DbTableMeta, DbTableMeta) {
+    (
+        DbTableMeta::new_default_dialect(String::from(input)),
+        DbTableMeta::new_default_dialect(String::from(output)),
+    )
+}
+
+#[test]
+fn statements_kept_separate() {
+    let statements = test_statements(
+        vec![
+            "INSERT INTO b SELECT * FROM a;\n  INSERT INTO c SELECT * FROM b JOIN x ON b.id = x.id;",
+            "SELECT * FROM c",
+        ],
+        &ParseOptions::default(),
+    );
+    assert_eq!(
+        statements
+            .iter()
+            .map(|s| (s.index, s.statement.as_str()))
+            .collect::<Vec<_>>(),
+        vec![
+            (0, "INSERT INTO b SELECT * FROM a"),
+            (1, "INSERT INTO c SELECT * FROM b JOIN x ON b.id = x.id"),
+            (2, "SELECT * FROM c"),
+        ]
+    );
+    assert_eq!(
+        statements[1].sql_meta,
+        TableLineage {
+            in_tables: tables(vec!["b", "x"]),
+            out_tables: table("c")
+        }
+    );
+    assert_eq!(
+        statements[2].sql_meta,
+        TableLineage {
+            in_tables: table("c"),
+            out_tables: vec![]
+        }
+    );
+}
+
+#[test]
+fn statements_table_edges() {
+    let statements = test_statements(
+        vec!["INSERT INTO b SELECT * FROM a; INSERT INTO c SELECT * FROM b JOIN x ON b.id = x.id"],
+        &ParseOptions::default(),
+    );
+    assert_eq!(statements[0].table_edges(), vec![edge("a", "b")]);
+    assert_eq!(
+        statements[1].table_edges(),
+        vec![edge("b", "c"), edge("x", "c")]
+    );
+}
+
+#[test]
+fn statements_merged_view_unchanged() {
+    let sql = vec!["INSERT INTO b SELECT * FROM a; INSERT INTO c SELECT * FROM b"];
+    assert_eq!(
+        test_multiple_sql(sql),
+        TableLineage {
+            in_tables: tables(vec!["a", "b"]),
+            out_tables: tables(vec!["b", "c"])
+        }
+    );
+}
+
+#[test]
+fn statements_tolerant_errors() {
+    let options = ParseOptions {
+        tolerant: true,
+        ..ParseOptions::default()
+    };
+    let statements = test_statements(
+        vec!["SELECT * FROM a; SELEC oops", "SELECT 'unterminated"],
+        &options,
+    );
+    assert_eq!(statements.len(), 3);
+    assert!(statements[0].sql_meta.errors.is_empty());
+    assert_eq!(statements[1].statement, "SELEC oops");
+    assert_eq!(statements[1].sql_meta.errors.len(), 1);
+    assert_eq!(statements[1].sql_meta.errors[0].index, 1);
+    assert_eq!(statements[2].statement, "SELECT 'unterminated");
+    assert_eq!(statements[2].sql_meta.errors[0].index, 2);
+}
@@ -0,0 +1,259 @@
+// Copyright 2018-2022 contributors to the OpenLineage project
+// SPDX-License-Identifier: Apache-2.0
+
+use openlineage_sql::{get_dialect, parse_sql, ParseOptions, SqlMeta, TablePattern};
+
+#[macro_use]
+mod test_utils;
+use test_utils::*;
+
+fn test_sql_exclude(sql: &str, patterns: Vec<&str>) -> SqlMeta {
+    let options = ParseOptions {
+        exclude: patterns
+            .into_iter()
+            .map(|pattern| pattern.parse().unwrap())
+            .collect(),
+        ..ParseOptions::default()
+    };
+    test_sql_options(sql, "postgres", &options)
+}
+
+#[test]
+fn exclude_information_schema() {
+    assert_eq!(
+        test_sql(
+            "INSERT INTO audit.columns SELECT c.column_name FROM information_schema.columns c \
+            JOIN orders o ON o.id = c.ordinal_position"
+        ),
+        TableLineage {
+            in_tables: table("orders"),
+            out_tables: table("audit.columns")
+        }
+    );
+}
+
+#[test]
+fn exclude_postgres_catalog() {
+    assert_eq!(
+        test_sql("SELECT * FROM pg_catalog.pg_class JOIN pg_stat_activity ON true"),
+        TableLineage {
+            in_tables: vec![],
+            out_tables: vec![]
+        }
+    );
+}
+
+#[test]
+fn keep_qualified_table_named_like_system_one() {
+    assert_eq!(
+        test_sql("SELECT * FROM app.pg_settings"),
+        TableLineage {
+            in_tables: table("app.pg_settings"),
+            out_tables: vec![]
+        }
+    );
+}
+
+#[test]
+fn exclude_redshift_system_tables() {
+    assert_eq!(
+        test_sql_dialect(
+            "INSERT INTO monitoring.loads SELECT * FROM stl_load_errors",
+            "redshift"
+        ),
+        TableLineage {
+            in_tables: vec![],
+            out_tables: table("monitoring.loads")
+        }
+    );
+}
+
+#[test]
+fn exclude_sql_server_sys_schema() {
+    assert_eq!(
+        test_sql_dialect(
+            "SELECT t.name FROM sys.tables t JOIN dbo.orders o ON o.name = t.name",
+            "mssql"
+        ),
+        TableLineage {
+            in_tables: table("dbo.orders"),
+            out_tables: vec![]
+        }
+    );
+}
+
+#[test]
+fn exclude_snowflake_account_usage() {
+    assert_eq!(
+        test_sql_dialect(
+            "INSERT INTO ops.history SELECT * FROM SNOWFLAKE.ACCOUNT_USAGE.QUERY_HISTORY",
+            "snowflake"
+        ),
+        TableLineage {
+            in_tables: vec![],
+            out_tables: table("OPS.HISTORY")
+        }
+    );
+}
+
+#[test]
+fn exclude_bigquery_information_schema() {
+    assert_eq!(
+        test_sql_dialect(
+            "SELECT * FROM `region-us`.INFORMATION_SCHEMA.JOBS \
+            UNION ALL SELECT * FROM project.dataset.__TABLES__",
+            "bigquery"
+        ),
+        TableLineage {
+            in_tables: vec![],
+            out_tables: vec![]
+        }
+    );
+}
+
+#[test]
+fn exclude_bigquery_project_qualified_information_schema() {
+    assert_eq!(
+        test_sql_dialect(
+            "INSERT INTO audit.tables SELECT * FROM proj.dataset.INFORMATION_SCHEMA.TABLES",
+            "bigquery"
+        ),
+        TableLineage {
+            in_tables: vec![],
+            out_tables: table("audit.tables")
+        }
+    );
+    assert_eq!(
+        test_sql_dialect(
+            "SELECT * FROM proj.`region-us`.INFORMATION_SCHEMA.JOBS",
+            "bigquery"
+        ),
+        TableLineage {
+            in_tables: vec![],
+            out_tables: vec![]
+        }
+    );
+}
+
+#[test]
+fn include_bigquery_project_qualified_information_schema() {
+    let options = ParseOptions {
+        include_system_tables: true,
+        ..ParseOptions::default()
+    };
+    let meta = test_sql_options(
+        "SELECT * FROM `proj.region-us.INFORMATION_SCHEMA.JOBS`",
+        "bigquery",
+        &options,
+    );
+    assert_eq!(
+        meta.in_tables[0].qualified_name(),
+        "proj.region-us.INFORMATION_SCHEMA.JOBS"
+    );
+}
+
+#[test]
+fn bigquery_table_with_four_parts_is_unsupported() {
+    assert!(parse_sql(
+        "SELECT * FROM proj.dataset.tables.extra",
+        get_dialect("bigquery"),
+        None
+    )
+    .is_err());
+}
+
+#[test]
+fn exclude_dual() {
+    assert_eq!(
+        test_sql_dialect("INSERT INTO t SELECT 1 FROM dual", "mysql"),
+        TableLineage {
+            in_tables: vec![],
+            out_tables: table("t")
+        }
+    );
+    assert_eq!(
+        test_sql_dialect("SELECT * FROM app.dual", "mysql"),
+        TableLineage {
+            in_tables: table("app.dual"),
+            out_tables: vec![]
+        }
+    );
+}
+
+#[test]
+fn include_system_tables() {
+    let options = ParseOptions {
+        include_system_tables: true,
+        ..ParseOptions::default()
+    };
+    assert_eq!(
+        test_sql_options(
+            "SELECT * FROM information_schema.tables",
+            "postgres",
+            &options
+        ),
+        TableLineage {
+            in_tables: table("information_schema.tables"),
+            out_tables: vec![]
+        }
+    );
+}
+
+#[test]
+fn exclude_system_columns_from_column_lineage() {
+    let meta = test_sql(
+        "INSERT INTO audit.tables (name, size) \
+        SELECT c.relname, o.size FROM pg_catalog.pg_class c JOIN orders o ON o.id = c.oid",
+    );
+    let lineage: Vec<(String, Vec<String>)> = meta
+        .column_lineage
+        .into_iter()
+        .map(|c| {
+            (
+                c.descendant.name,
+                c.lineage.into_iter().map(|l| l.name).collect(),
+            )
+        })
+        .collect();
+    assert_eq!(
+        lineage,
+        vec![
+            (String::from("name"), vec![]),
+            (String::from("size"), vec![String::from("size")])
+        ]
+    );
+}
+
+#[test]
+fn exclude_glob() {
+    assert_eq!(
+        test_sql_exclude(
+            "INSERT INTO staging.tmp_orders SELECT * FROM orders JOIN Scratch.Items ON true",
+            vec!["staging.tmp_*", "scratch.*"]
+        ),
+        TableLineage {
+            in_tables: table("orders"),
+            out_tables: vec![]
+        }
+    );
+}
+
+#[test]
+fn exclude_regex() {
+    assert_eq!(
+        test_sql_exclude(
+            "SELECT * FROM backup_2022_01 JOIN backup_old ON true",
+            vec!["regex:backup_\\d{4}_\\d{2}"]
+        ),
+        TableLineage {
+            in_tables: table("backup_old"),
+            out_tables: vec![]
+        }
+    );
+}
+
+#[test]
+fn invalid_regex() {
+    assert!(TablePattern::regex("backup_(").is_err());
+    assert!("glob:backup_(".parse::<TablePattern>().is_ok());
+}
@@ -0,0 +1,148 @@
+// Copyright 2018-2022 contributors to the OpenLineage project
+// SPDX-License-Identifier: Apache-2.0
+
+use openlineage_sql::{
+    get_dialect, parse_multiple_statements_with_options, parse_statements_with_options,
+    ColumnLineage, ParseOptions,
+};
+
+#[macro_use]
+mod test_utils;
+use test_utils::*;
+
+#[test]
+fn temporary_table_eliminated() {
+    assert_eq!(
+        test_multiple_sql(vec![
+            "CREATE TEMP TABLE tmp AS SELECT id, amount FROM a",
+            "INSERT INTO final SELECT t.id, t.amount * b.rate AS amount FROM tmp t JOIN b ON t.id = b.id",
+            "DROP TABLE tmp",
+        ]),
+        TableLineage {
+            in_tables: tables(vec!["a", "b"]),
+            out_tables: table("final")
+        }
+    );
+}
+
+#[test]
+fn temporary_table_column_lineage() {
+    assert_eq!(
+        test_multiple_sql(vec![
+            "CREATE TEMPORARY TABLE tmp AS SELECT id, amount FROM a",
+            "INSERT INTO final SELECT t.id, t.amount * b.rate AS amount FROM tmp t JOIN b ON t.id = b.id",
+        ])
+        .column_lineage,
+        vec![
+            ColumnLineage {
+                descendant: column("final", "amount"),
+                lineage: vec![column("a", "amount"), column("b", "rate")]
+            },
+            ColumnLineage {
+                descendant: column("final", "id"),
+                lineage: vec![column("a", "id")]
+            },
+        ]
+    );
+}
+
+#[test]
+fn temporary_table_chain() {
+    assert_eq!(
+        test_multiple_sql(vec![
+            "CREATE TEMP TABLE tmp1 (id INT)",
+            "INSERT INTO tmp1 SELECT id FROM a",
+            "CREATE TEMP TABLE tmp2 AS SELECT * FROM tmp1 JOIN b ON tmp1.id = b.id",
+            "INSERT INTO final SELECT * FROM tmp2",
+        ]),
+        TableLineage {
+            in_tables: tables(vec!["a", "b"]),
+            out_tables: table("final")
+        }
+    );
+}
+
+#[test]
+fn temporary_mssql_session_table() {
+    assert_eq!(
+        test_multiple_sql_dialect(
+            vec!["SELECT * INTO #staging FROM dbo.src; INSERT INTO dbo.tgt SELECT * FROM #staging"],
+            "mssql"
+        ),
+        TableLineage {
+            in_tables: table("dbo.src"),
+            out_tables: table("dbo.tgt")
+        }
+    );
+}
+
+#[test]
+fn temporary_volatile_table() {
+    assert_eq!(
+        test_multiple_sql_dialect(
+            vec![
+                "CREATE VOLATILE TABLE vt AS SELECT * FROM src",
+                "INSERT INTO tgt SELECT * FROM vt"
+            ],
+            "snowflake"
+        ),
+        TableLineage {
+            in_tables: table("SRC"),
+            out_tables: table("TGT")
+        }
+    );
+}
+
+#[test]
+fn temporary_table_kept() {
+    let options = ParseOptions {
+        keep_temporary_tables: true,
+        ..ParseOptions::default()
+    };
+    let sql = vec!["CREATE TEMP TABLE tmp AS SELECT * FROM a; INSERT INTO final SELECT * FROM tmp"];
+    assert_eq!(
+        parse_multiple_statements_with_options(sql.clone(), get_dialect("postgres"), &options)
+            .unwrap(),
+        TableLineage {
+            in_tables: tables(vec!["a", "tmp"]),
+            out_tables: tables(vec!["final", "tmp"])
+        }
+    );
+    let statements =
+        parse_statements_with_options(sql, get_dialect("postgres"), &ParseOptions::default())
+            .unwrap();
+    assert_eq!(statements[0].temporary_tables, table("tmp"));
+    assert_eq!(statements[1].temporary_tables, vec![]);
+}
+
+#[test]
+fn temporary_table_created_after_write_to_persistent_table() {
+    assert_eq!(
+        test_multiple_sql(vec![
+            "INSERT INTO t SELECT * FROM a",
+            "CREATE TEMP TABLE t AS SELECT * FROM b",
+            "INSERT INTO c SELECT * FROM t",
+        ]),
+        TableLineage {
+            in_tables: tables(vec!["a", "b"]),
+            out_tables: tables(vec!["c", "t"])
+        }
+    );
+}
+
+#[test]
+fn temporary_table_renamed() {
+    let meta = test_multiple_sql(vec![
+        "CREATE TEMP TABLE tmp AS SELECT * FROM src",
+        "ALTER TABLE tmp RENAME TO tmp2",
+        "INSERT INTO dst SELECT * FROM tmp2",
+    ]);
+    assert_eq!(
+        meta,
+        TableLineage {
+            in_tables: table("src"),
+            out_tables: table("dst")
+        }
+    );
+    assert_eq!(meta.renames, vec![]);
+}
@@ -1,7 +1,6 @@
 // Copyright 2018-2022 contributors to the OpenLineage project
 // SPDX-License-Identifier: Apache-2.0
 
-use openlineage_sql::SqlMeta;
 
 #[macro_use]
 mod test_utils;
@@ -84,7 +83,7 @@ fn test_tpcds_cte_query() {
                         t_s_secyear.customer_preferred_cust_flag
             LIMIT 100;
             ",
-    ), SqlMeta {
+    ), TableLineage {
         in_tables: tables(vec![
             "date_dim",
             "store_sales",
@@ -122,7 +121,7 @@ fn test_tcpds_query_1() {
         ORDER  BY c_customer_id
         LIMIT 100;",
         ),
-        SqlMeta {
+        TableLineage {
             in_tables: tables(vec!["customer", "date_dim", "store", "store_returns",]),
             out_tables: vec![]
         }
@@ -213,7 +212,7 @@ fn test_tcpds_query_2() {
     WHERE  d_week_seq1 = d_week_seq2 - 53
     ORDER  BY d_week_seq1;",
         ),
-        SqlMeta {
+        TableLineage {
             in_tables: tables(vec!["catalog_sales", "date_dim", "web_sales",]),
             out_tables: vec![]
         }
@@ -245,7 +244,7 @@ fn test_tcpds_query_3() {
         LIMIT 100;
         ",
         ),
-        SqlMeta {
+        TableLineage {
             in_tables: tables(vec!["date_dim", "item", "store_sales",]),
             out_tables: vec![]
         }
@@ -410,7 +409,7 @@ fn test_tcpds_query_4() {
         LIMIT 100;
         ",
         ),
-        SqlMeta {
+        TableLineage {
             in_tables: tables(vec![
                 "catalog_sales",
                 "customer",
@@ -552,7 +551,7 @@ fn test_tcpds_query_5() {
             ORDER BY channel ,
                      id
             LIMIT 100; ",
-    ), SqlMeta {
+    ), TableLineage {
         in_tables: tables(vec![
             "catalog_page",
             "catalog_returns",
@@ -598,7 +597,7 @@ fn test_tcpds_query_6() {
         LIMIT 100;
     ",
         ),
-        SqlMeta {
+        TableLineage {
             in_tables: tables(vec![
                 "customer",
                 "customer_address",
@@ -641,7 +640,7 @@ fn test_tcpds_query_7() {
         LIMIT 100;
     ",
         ),
-        SqlMeta {
+        TableLineage {
             in_tables: tables(vec![
                 "customer_demographics",
                 "date_dim",
@@ -703,7 +702,7 @@ fn test_tcpds_query_8() {
         LIMIT 100;
     ",
         ),
-        SqlMeta {
+        TableLineage {
             in_tables: tables(vec![
                 "customer",
                 "customer_address",
@@ -785,7 +784,7 @@ fn test_tcpds_query_9() {
         WHERE  r_reason_sk = 1;
     ",
         ),
-        SqlMeta {
+        TableLineage {
             in_tables: tables(vec!["reason", "store_sales"]),
             out_tables: vec![]
         }
@@ -1,7 +1,6 @@
 // Copyright 2018-2022 contributors to the OpenLineage project
 // SPDX-License-Identifier: Apache-2.0
 
-use openlineage_sql::SqlMeta;
 
 #[macro_use]
 mod test_utils;
@@ -11,7 +10,7 @@ use test_utils::*;
 fn update_table() {
     assert_eq!(
         test_sql("UPDATE table0 SET col0 = val0 WHERE col1 = val1"),
-        SqlMeta {
+        TableLineage {
             in_tables: vec![],
             out_tables: table("table0")
         }
@@ -28,9 +27,9 @@ fn update_table_from() {
             FROM dataset.NewArrivals n
             WHERE i.product = n.product"
         ),
-        SqlMeta {
-            in_tables: table("dataset.NewArrivals"),
-            out_tables: table("dataset.Inventory")
+        TableLineage {
+            in_tables: table("dataset.newarrivals"),
+            out_tables: table("dataset.inventory")
         }
     )
 }
@@ -46,8 +45,25 @@ fn update_table_from_subquery() {
             supply_constrained = false
             WHERE product IN (SELECT product FROM dataset.NewArrivals)"
         ),
-        SqlMeta {
-            in_tables: table("dataset.NewArrivals"),
+        TableLineage {
+            in_tables: table("dataset.newarrivals"),
+            out_tables: table("dataset.inventory")
+        }
+    )
+}
+
+#[test]
+fn update_table_alias_from() {
+    assert_eq!(
+        test_sql_dialect(
+            "UPDATE i
+            SET quantity = n.quantity
+            FROM dataset.Inventory i
+            JOIN dataset.NewArrivals n ON i.product = n.product",
+            "mssql"
+        ),
+        TableLineage {
+            in_tables: tables(vec!["dataset.Inventory", "dataset.NewArrivals"]),
             out_tables: table("dataset.Inventory")
         }
     )
@@ -0,0 +1,174 @@
+// Copyright 2018-2022 contributors to the OpenLineage project
+// SPDX-License-Identifier: Apache-2.0
+
+use openlineage_sql::{
+    get_dialect, parse_sql, parse_statements_with_options, ColumnLineage, DatasetOperation,
+    DbTableMeta, Operation, ParseOptions,
+};
+
+#[macro_use]
+mod test_utils;
+use test_utils::*;
+
+#[test]
+fn create_view() {
+    assert_eq!(
+        test_sql("CREATE VIEW active_users AS SELECT * FROM users WHERE active"),
+        TableLineage {
+            in_tables: table("users"),
+            out_tables: table("active_users")
+        }
+    )
+}
+
+#[test]
+fn create_or_replace_materialized_view() {
+    let meta = test_sql(
+        "CREATE OR REPLACE MATERIALIZED VIEW totals AS \
+        SELECT o.customer_id, SUM(i.amount) FROM orders o JOIN items i ON o.id = i.order_id \
+        GROUP BY o.customer_id",
+    );
+    assert_eq!(
+        meta,
+        TableLineage {
+            in_tables: tables(vec!["items", "orders"]),
+            out_tables: table("totals")
+        }
+    );
+    assert_eq!(
+        meta.operations,
+        vec![DatasetOperation::new(
+            DbTableMeta::new_default_dialect(String::from("totals")),
+            Operation::CreateOrReplace
+        )]
+    );
+}
+
+#[test]
+fn create_view_column_lineage() {
+    assert_eq!(
+        test_sql("CREATE VIEW v (user_id, total) AS SELECT id, amount * 2 FROM orders")
+            .column_lineage,
+        vec![
+            ColumnLineage {
+                descendant: column("v", "total"),
+                lineage: vec![column("orders", "amount")]
+            },
+            ColumnLineage {
+                descendant: column("v", "user_id"),
+                lineage: vec![column("orders", "id")]
+            },
+        ]
+    )
+}
+
+#[test]
+fn create_snowflake_secure_view() {
+    assert_eq!(
+        test_sql_dialect(
+            "CREATE OR REPLACE SECURE VIEW IF NOT EXISTS mart.v COPY GRANTS AS SELECT * FROM raw.t",
+            "snowflake"
+        ),
+        TableLineage {
+            in_tables: table("RAW.T"),
+            out_tables: table("MART.V")
+        }
+    )
+}
+
+#[test]
+fn create_redshift_late_binding_view() {
+    assert_eq!(
+        test_sql_dialect(
+            "CREATE VIEW mart.v AS SELECT * FROM spectrum.events WITH NO SCHEMA BINDING",
+            "redshift"
+        ),
+        TableLineage {
+            in_tables: table("spectrum.events"),
+            out_tables: table("mart.v")
+        }
+    )
+}
+
+#[test]
+fn create_view_malformed_if() {
+    assert!(parse_sql("CREATE VIEW IF AS", get_dialect("snowflake"), None).is_err());
+}
+
+#[test]
+fn create_bigquery_view_with_options() {
+    assert_eq!(
+        test_sql_dialect(
+            "CREATE VIEW `project.shared.v` OPTIONS (description = 'authorized (shared)') AS \
+            SELECT * FROM `project.private.t`",
+            "bigquery"
+        ),
+        TableLineage {
+            in_tables: table("project.private.t"),
+            out_tables: table("project.shared.v")
+        }
+    )
+}
+
+#[test]
+fn read_view_created_in_script() {
+    let meta = test_multiple_sql(vec![
+        "CREATE VIEW recent AS SELECT id, amount FROM orders WHERE day > now() - 7",
+        "INSERT INTO report (total) SELECT SUM(amount) FROM recent",
+    ]);
+    assert_eq!(
+        meta,
+        TableLineage {
+            in_tables: table("orders"),
+            out_tables: tables(vec!["recent", "report"])
+        }
+    );
+    assert_eq!(
+        meta.column_lineage,
+        vec![
+            ColumnLineage {
+                descendant: column("recent", "amount"),
+                lineage: vec![column("orders", "amount")]
+            },
+            ColumnLineage {
+                descendant: column("recent", "id"),
+                lineage: vec![column("orders", "id")]
+            },
+            ColumnLineage {
+                descendant: column("report", "total"),
+                lineage: vec![column("orders", "amount")]
+            },
+        ]
+    );
+}
+
+#[test]
+fn read_view_in_later_statement() {
+    let statements = parse_statements_with_options(
+        vec!["CREATE VIEW v AS SELECT * FROM a JOIN b ON a.id = b.id; SELECT * FROM v JOIN c ON true"],
+        get_dialect("postgres"),
+        &ParseOptions::default(),
+    )
+    .unwrap();
+    assert_eq!(
+        statements[1].sql_meta,
+        TableLineage {
+            in_tables: tables(vec!["a", "b", "c"]),
+            out_tables: vec![]
+        }
+    )
+}
+
+#[test]
+fn view_is_not_known_before_it_is_created() {
+    assert_eq!(
+        test_multiple_sql(vec![
+            "INSERT INTO report SELECT * FROM v",
+            "CREATE OR REPLACE VIEW v AS SELECT * FROM t",
+        ]),
+        TableLineage {
+            in_tables: tables(vec!["t", "v"]),
+            out_tables: tables(vec!["report", "v"])
+        }
+    )
+}